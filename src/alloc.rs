@@ -0,0 +1,152 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// The system allocator, counting what each thread allocates once
+/// `enable` is called. It has to be the `#[global_allocator]`, as in the
+/// binary; until enabled it only costs a check of a flag. A `realloc`
+/// counts as an allocation of its new size.
+pub struct Counting;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Totals for one thread since it started. `live` can go below 0 when the
+/// thread frees what others allocated.
+#[derive(Clone, Copy)]
+struct Counts {
+    allocations: usize,
+    bytes: usize,
+    live: isize,
+    peak: isize,
+}
+
+thread_local! {
+    static COUNTS: Cell<Counts> = const {
+        Cell::new(Counts {
+            allocations: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+fn count(allocated: usize, freed: usize) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    // not there while the thread is torn down, and then it doesn't matter
+    let _ = COUNTS.try_with(|counts| {
+        let mut c = counts.get();
+        if allocated > 0 {
+            c.allocations += 1;
+            c.bytes += allocated;
+        }
+        c.live += allocated as isize - freed as isize;
+        c.peak = c.peak.max(c.live);
+        counts.set(c);
+    });
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ret = System.alloc(layout);
+        if !ret.is_null() {
+            count(layout.size(), 0);
+        }
+        ret
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ret = System.alloc_zeroed(layout);
+        if !ret.is_null() {
+            count(layout.size(), 0);
+        }
+        ret
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        count(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let ret = System.realloc(ptr, layout, new_size);
+        if !ret.is_null() {
+            count(new_size, layout.size());
+        }
+        ret
+    }
+}
+
+/// Starts counting allocations, in every thread. Only does something if
+/// `Counting` is the global allocator.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// What a piece of code allocated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub bytes: usize,
+    /// the most it had allocated and not freed yet at any time
+    pub peak: usize,
+}
+
+/// Calls `f`, and says what it allocated in this thread, if counting.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !enabled() {
+        return (f(), None);
+    }
+    let before = COUNTS.with(|counts| {
+        let mut c = counts.get();
+        c.peak = c.live;
+        counts.set(c);
+        c
+    });
+    let ret = f();
+    let after = COUNTS.with(Cell::get);
+    let stats = AllocStats {
+        allocations: after.allocations - before.allocations,
+        bytes: after.bytes - before.bytes,
+        peak: (after.peak - before.live).max(0) as usize,
+    };
+    (ret, Some(stats))
+}
+
+/// `12B`, `3.40KiB`, `1.23MiB` or `2.00GiB`.
+pub fn format_bytes(bytes: usize) -> String {
+    let units = ["KiB", "MiB", "GiB"];
+    if bytes < 1024 {
+        return format!("{bytes}B");
+    }
+    let mut value = bytes as f64 / 1024.;
+    for unit in &units[..units.len() - 1] {
+        if value < 1024. {
+            return format!("{value:.2}{unit}");
+        }
+        value /= 1024.;
+    }
+    format!("{value:.2}{}", units[units.len() - 1])
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::format_bytes;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(12), "12B");
+        assert_eq!(format_bytes(3482), "3.40KiB");
+        assert_eq!(format_bytes(1_289_748), "1.23MiB");
+        assert_eq!(format_bytes(2 << 30), "2.00GiB");
+        assert_eq!(format_bytes(3 << 40), "3072.00GiB");
+    }
+}
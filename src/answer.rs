@@ -0,0 +1,111 @@
+use std::fmt;
+
+/// The answer to one part of a day.
+///
+/// Integers that fit in an `i64` are always `Int`, so that equal numbers
+/// compare equal whatever type the solver computed them in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    BigInt(i128),
+    Text(String),
+    Coord(i64, i64),
+}
+
+impl Answer {
+    /// Name of the variant, for machine readable output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Int(_) => "int",
+            Answer::BigInt(_) => "bigint",
+            Answer::Text(_) => "text",
+            Answer::Coord(..) => "coord",
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::BigInt(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Coord(x, y) => write!(f, "{x},{y}"),
+        }
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        match i64::try_from(n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::BigInt(n),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::from(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i32, i64, isize, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl From<(isize, isize)> for Answer {
+    fn from((x, y): (isize, isize)) -> Self {
+        Answer::Coord(x as i64, y as i64)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::Answer;
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Answer::from(3usize), Answer::Int(3));
+        assert_eq!(Answer::from(-3isize), Answer::Int(-3));
+        assert_eq!(Answer::from(3i32), Answer::from(3u64));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInt(u64::MAX as i128));
+        assert_eq!(Answer::from("co,de"), Answer::Text("co,de".to_string()));
+        assert_eq!(Answer::from((6, 1)), Answer::Coord(6, 1));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::Int(-12).to_string(), "-12");
+        assert_eq!(
+            Answer::BigInt(1 << 70).to_string(),
+            "1180591620717411303424"
+        );
+        assert_eq!(Answer::from("4,6,3").to_string(), "4,6,3");
+        assert_eq!(Answer::Coord(6, 1).to_string(), "6,1");
+    }
+
+    #[test]
+    fn test_kind() {
+        assert_eq!(Answer::from(3usize).kind(), "int");
+        assert_eq!(Answer::from(u64::MAX).kind(), "bigint");
+        assert_eq!(Answer::from("x").kind(), "text");
+        assert_eq!(Answer::from((6, 1)).kind(), "coord");
+    }
+}
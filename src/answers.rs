@@ -0,0 +1,292 @@
+use std::{collections::BTreeMap, fmt, fs, io, path::Path};
+
+use crate::{
+    answer::Answer,
+    part::Part,
+    solution::{day_label, DEFAULT_YEAR},
+};
+
+pub const DEFAULT_PATH: &str = "answers.toml";
+
+/// Answers known to be right, to check the solvers against.
+///
+/// They are kept in a small subset of TOML, one table per day:
+///
+/// ```toml
+/// [day_17]
+/// part1 = "4,6,3,5,6,3,5,2,1,0"
+/// part2 = 117440
+///
+/// [2023.day_1]
+/// part1 = 54634
+/// ```
+///
+/// Days of other years than the default one have the year in front.
+///
+/// Answers are compared by how they are displayed, so a coordinate
+/// recorded as `"6,1"` matches `Answer::Coord(6, 1)`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    recorded: BTreeMap<(usize, usize, Part), String>,
+}
+
+/// How a computed answer compares to the recorded one.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Ok,
+    Mismatch { expected: String },
+    Missing,
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io(io::Error),
+    Syntax { line: usize, message: String },
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswersError::Io(e) => write!(f, "{e}"),
+            AnswersError::Syntax { line, message } => write!(f, "line {line}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AnswersError::Io(e) => Some(e),
+            AnswersError::Syntax { .. } => None,
+        }
+    }
+}
+
+pub(crate) fn is_int(value: &str) -> bool {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+pub(crate) fn unquote(value: &str) -> Option<String> {
+    let inner = value.strip_prefix('"')?.strip_suffix('"')?;
+    let mut ret = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                c @ ('"' | '\\') => ret.push(c),
+                _ => return None,
+            },
+            '"' => return None,
+            c => ret.push(c),
+        }
+    }
+    Some(ret)
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// `day_N` or `YEAR.day_N`, as year and day.
+fn parse_table(table: &str) -> Option<(usize, usize)> {
+    let (year, day) = match table.split_once('.') {
+        Some((year, day)) => (year.parse().ok()?, day),
+        None => (DEFAULT_YEAR, table),
+    };
+    Some((year, day.strip_prefix("day_")?.parse().ok()?))
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, AnswersError> {
+        let mut ret = Answers::default();
+        let mut day: Option<(usize, usize)> = None;
+        for (n, line) in text.lines().enumerate() {
+            let syntax = |message: String| AnswersError::Syntax {
+                line: n + 1,
+                message,
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(table) = line.strip_prefix('[') {
+                match table.strip_suffix(']').and_then(|t| parse_table(t.trim())) {
+                    Some(d) => day = Some(d),
+                    None => {
+                        return Err(syntax(format!(
+                            "expected `[day_N]` or `[YEAR.day_N]`, found `{line}`"
+                        )))
+                    }
+                }
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(syntax(format!("expected `partN = answer`, found `{line}`")));
+            };
+            let Some((year, day)) = day else {
+                return Err(syntax("answer given before any `[day_N]`".to_string()));
+            };
+            let part = match key.trim().strip_prefix("part").map(|p| p.parse::<Part>()) {
+                Some(Ok(part)) => part,
+                _ => return Err(syntax(format!("unknown key `{}`", key.trim()))),
+            };
+            let value = value.trim();
+            let answer = if is_int(value) {
+                value.to_string()
+            } else {
+                unquote(value).ok_or_else(|| syntax(format!("can't read answer `{value}`")))?
+            };
+            if ret.recorded.insert((year, day, part), answer).is_some() {
+                let label = day_label(year, day);
+                return Err(syntax(format!("{label} part {part} given twice")));
+            }
+        }
+        Ok(ret)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        let text = fs::read_to_string(path).map_err(AnswersError::Io)?;
+        Self::parse(&text)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, year: usize, day: usize, part: Part) -> Option<&str> {
+        self.recorded.get(&(year, day, part)).map(|a| a.as_str())
+    }
+
+    pub fn record(&mut self, year: usize, day: usize, part: Part, answer: &Answer) {
+        self.recorded.insert((year, day, part), answer.to_string());
+    }
+
+    pub fn check(&self, year: usize, day: usize, part: Part, answer: &Answer) -> Verdict {
+        match self.get(year, day, part) {
+            None => Verdict::Missing,
+            Some(expected) if expected == answer.to_string() => Verdict::Ok,
+            Some(expected) => Verdict::Mismatch {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut last_day = None;
+        for (&(year, day, part), answer) in &self.recorded {
+            if last_day != Some((year, day)) {
+                if last_day.is_some() {
+                    writeln!(f)?;
+                }
+                if year == DEFAULT_YEAR {
+                    writeln!(f, "[day_{day}]")?;
+                } else {
+                    writeln!(f, "[{year}.day_{day}]")?;
+                }
+                last_day = Some((year, day));
+            }
+            if is_int(answer) {
+                writeln!(f, "part{part} = {answer}")?;
+            } else {
+                writeln!(f, "part{part} = {}", quote(answer))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::{Answers, AnswersError, Verdict};
+    use crate::{answer::Answer, part::Part};
+
+    const TEXT: &str = "\
+# answers for the examples
+[day_11]
+part1 = 55312
+
+[day_17]
+part1 = \"4,6,3,5,6,3,5,2,1,0\"
+part2 = 117440
+
+[day_18]
+part2 = \"6,1\"
+
+[2023.day_1]
+part1 = 142
+";
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(TEXT).unwrap();
+        assert_eq!(answers.get(2024, 11, Part::One), Some("55312"));
+        assert_eq!(answers.get(2024, 11, Part::Two), None);
+        assert_eq!(
+            answers.get(2024, 17, Part::One),
+            Some("4,6,3,5,6,3,5,2,1,0")
+        );
+        assert_eq!(answers.get(2024, 17, Part::Two), Some("117440"));
+        assert_eq!(answers.get(2024, 18, Part::Two), Some("6,1"));
+        assert_eq!(answers.get(2023, 1, Part::One), Some("142"));
+        assert_eq!(answers.get(2024, 1, Part::One), None);
+
+        let line_of = |text: &str| match Answers::parse(text) {
+            Err(AnswersError::Syntax { line, .. }) => line,
+            other => panic!("{other:?} is not a syntax error"),
+        };
+        assert_eq!(line_of("part1 = 3"), 1);
+        assert_eq!(line_of("[day_1]\npart3 = 3"), 2);
+        assert_eq!(line_of("[day_1]\n\npart1 = abc"), 3);
+        assert_eq!(line_of("[day_1]\npart1 = \"a\"b\""), 2);
+        assert_eq!(line_of("[day_1]\npart1 = 1\npart1 = 2"), 3);
+        assert_eq!(line_of("[dia_1]"), 1);
+        assert_eq!(line_of("[2023day_1]"), 1);
+        assert_eq!(
+            line_of("[2023.day_1]\npart1 = 1\n[2023.day_1]\npart1 = 2"),
+            4
+        );
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let answers = Answers::parse(TEXT).unwrap();
+        let text = answers.to_string();
+        assert!(text.starts_with("[2023.day_1]\npart1 = 142\n\n[day_11]\npart1 = 55312\n\n"));
+        assert_eq!(Answers::parse(&text).unwrap(), answers);
+
+        let mut answers = Answers::default();
+        answers.record(2024, 3, Part::Two, &Answer::from("say \"hi\" \\o/"));
+        assert_eq!(Answers::parse(&answers.to_string()).unwrap(), answers);
+    }
+
+    #[test]
+    fn test_check() {
+        let mut answers = Answers::parse(TEXT).unwrap();
+        assert_eq!(
+            answers.check(2024, 11, Part::One, &Answer::Int(55312)),
+            Verdict::Ok
+        );
+        assert_eq!(
+            answers.check(2024, 18, Part::Two, &Answer::Coord(6, 1)),
+            Verdict::Ok
+        );
+        assert_eq!(
+            answers.check(2024, 11, Part::One, &Answer::Int(55313)),
+            Verdict::Mismatch {
+                expected: "55312".to_string()
+            }
+        );
+        assert_eq!(
+            answers.check(2024, 11, Part::Two, &Answer::Int(1)),
+            Verdict::Missing
+        );
+        answers.record(2024, 11, Part::Two, &Answer::Int(1));
+        assert_eq!(
+            answers.check(2024, 11, Part::Two, &Answer::Int(1)),
+            Verdict::Ok
+        );
+    }
+}
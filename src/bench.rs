@@ -0,0 +1,324 @@
+use std::{collections::BTreeMap, fmt, fmt::Write, fs, io, path::Path, time::Duration};
+
+use crate::{
+    part::Part,
+    runner::{format_duration, timed, RunError},
+    solution::{short_day_label, Params, Solver, DEFAULT_YEAR},
+};
+
+pub const DEFAULT_ITERATIONS: usize = 10;
+
+/// What gets timed: parsing the input, or solving one part of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Solve(part) => write!(f, "part {part}"),
+        }
+    }
+}
+
+impl Phase {
+    /// How it is written in baseline files.
+    fn key(&self) -> String {
+        match self {
+            Phase::Parse => "parse".to_string(),
+            Phase::Solve(part) => part.to_string(),
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "parse" => Some(Phase::Parse),
+            _ => key.parse().ok().map(Phase::Solve),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples to summarize");
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        };
+        let secs: Vec<f64> = sorted.iter().map(|d| d.as_secs_f64()).collect();
+        let mean = secs.iter().sum::<f64>() / n as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n as f64;
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Parses `input` and solves `parts` of it `iterations` times, after a few
+/// untimed rounds to warm up caches and the allocator. Fails before timing
+/// anything if `input` can't be parsed, or `params` don't suit it.
+pub fn bench(
+    solver: &dyn Solver,
+    input: &str,
+    parts: &[Part],
+    params: &Params,
+    iterations: usize,
+) -> Result<Vec<(Phase, Stats)>, RunError> {
+    let parsed = solver.parse(input)?;
+    solver.check(&parsed, params).map_err(RunError::Params)?;
+    let warmup = (iterations / 10).max(1);
+    let mut samples: Vec<(Phase, Vec<Duration>)> = [Phase::Parse]
+        .into_iter()
+        .chain(parts.iter().map(|&p| Phase::Solve(p)))
+        .map(|phase| (phase, vec![]))
+        .collect();
+    for round in 0..warmup + iterations {
+        let (parsed, parse) = timed(|| solver.parse(input));
+        let parsed = parsed?;
+        let mut durations = vec![parse];
+        for &part in parts {
+            durations.push(timed(|| solver.solve(&parsed, part, params)).1);
+        }
+        if round >= warmup {
+            for ((_, phase_samples), d) in samples.iter_mut().zip(durations) {
+                phase_samples.push(d);
+            }
+        }
+    }
+    Ok(samples
+        .into_iter()
+        .map(|(phase, s)| (phase, Stats::from_samples(&s)))
+        .collect())
+}
+
+/// Results of earlier benchmarks to compare with, one line per day and phase,
+/// with the year in front of days of other years than the default one:
+///
+/// ```text
+/// # day phase min_ns median_ns mean_ns stddev_ns
+/// 9 parse 20100 20510 20998 800
+/// 9 1 21610 21800 22010 300
+/// 2023/9 1 1610 1800 2010 30
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Baseline {
+    stats: BTreeMap<(usize, usize, Phase), Stats>,
+}
+
+/// `N` or `YEAR/N`, as year and day.
+fn parse_day(text: &str) -> Option<(usize, usize)> {
+    match text.split_once('/') {
+        Some((year, day)) => Some((year.parse().ok()?, day.parse().ok()?)),
+        None => Some((DEFAULT_YEAR, text.parse().ok()?)),
+    }
+}
+
+impl Baseline {
+    /// `Err` has the number of the line that could not be read.
+    pub fn parse(text: &str) -> Result<Self, usize> {
+        let mut ret = Baseline::default();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [day, phase, min, median, mean, stddev] = fields[..] else {
+                return Err(n + 1);
+            };
+            let (year, day) = parse_day(day).ok_or(n + 1)?;
+            let phase = Phase::from_key(phase).ok_or(n + 1)?;
+            let ns = |s: &str| s.parse().map(Duration::from_nanos).map_err(|_| n + 1);
+            let stats = Stats {
+                min: ns(min)?,
+                median: ns(median)?,
+                mean: ns(mean)?,
+                stddev: ns(stddev)?,
+            };
+            ret.stats.insert((year, day, phase), stats);
+        }
+        Ok(ret)
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::parse(&text).map_err(|line| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("can't understand line {line}"),
+            )
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, year: usize, day: usize, phase: Phase) -> Option<&Stats> {
+        self.stats.get(&(year, day, phase))
+    }
+
+    pub fn insert(&mut self, year: usize, day: usize, phase: Phase, stats: Stats) {
+        self.stats.insert((year, day, phase), stats);
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# day phase min_ns median_ns mean_ns stddev_ns")?;
+        for ((year, day, phase), s) in &self.stats {
+            writeln!(
+                f,
+                "{} {} {} {} {} {}",
+                short_day_label(*year, *day),
+                phase.key(),
+                s.min.as_nanos(),
+                s.median.as_nanos(),
+                s.mean.as_nanos(),
+                s.stddev.as_nanos()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// One row per phase, and the change in median against `baseline` if given.
+pub fn table(
+    year: usize,
+    day: usize,
+    results: &[(Phase, Stats)],
+    baseline: Option<&Baseline>,
+) -> String {
+    let mut ret = String::new();
+    let mut header = format!(
+        "{:<8}{:>11}{:>11}{:>11}{:>11}",
+        "", "min", "median", "mean", "stddev"
+    );
+    if baseline.is_some() {
+        write!(header, "{:>11}{:>9}", "baseline", "change").unwrap();
+    }
+    writeln!(ret, "{}", header.trim_end()).unwrap();
+    for (phase, s) in results {
+        write!(
+            ret,
+            "{:<8}{:>11}{:>11}{:>11}{:>11}",
+            phase.to_string(),
+            format_duration(s.min),
+            format_duration(s.median),
+            format_duration(s.mean),
+            format_duration(s.stddev)
+        )
+        .unwrap();
+        match baseline.map(|b| b.get(year, day, *phase)) {
+            Some(Some(base)) => {
+                let change = s.median.as_secs_f64() / base.median.as_secs_f64() - 1.;
+                write!(
+                    ret,
+                    "{:>11}{:>9}",
+                    format_duration(base.median),
+                    format!("{:+.1}%", change * 100.)
+                )
+                .unwrap();
+            }
+            Some(None) => write!(ret, "{:>11}{:>9}", "-", "-").unwrap(),
+            None => {}
+        }
+        writeln!(ret).unwrap();
+    }
+    ret
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::time::Duration;
+
+    use super::{bench, table, Baseline, Phase, Stats};
+    use crate::{part::Part, registry, solution::Params};
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&[ms(4), ms(2), ms(6), ms(4)]);
+        assert_eq!(stats.min, ms(2));
+        assert_eq!(stats.median, ms(4));
+        assert_eq!(stats.mean, ms(4));
+        assert_eq!(stats.stddev.as_micros(), 1414);
+        assert_eq!(Stats::from_samples(&[ms(3), ms(1), ms(9)]).median, ms(3));
+    }
+
+    #[test]
+    fn test_bench() {
+        let registry = registry::all();
+        let solver = registry.get(2024, 11).unwrap();
+        let params = Params::defaults(solver.params());
+        let results = bench(solver, "125 17", &[Part::One], &params, 3).unwrap();
+        let phases: Vec<Phase> = results.iter().map(|(p, _)| *p).collect();
+        assert_eq!(phases, vec![Phase::Parse, Phase::Solve(Part::One)]);
+        for (_, stats) in results {
+            assert!(stats.min <= stats.median);
+        }
+        assert!(bench(solver, "125 1x", &[Part::One], &params, 3).is_err());
+    }
+
+    #[test]
+    fn test_baseline() {
+        let stats = Stats {
+            min: ms(1),
+            median: ms(2),
+            mean: ms(3),
+            stddev: ms(4),
+        };
+        let mut baseline = Baseline::default();
+        baseline.insert(2024, 9, Phase::Solve(Part::Two), stats);
+        baseline.insert(2024, 9, Phase::Parse, stats);
+        baseline.insert(2023, 9, Phase::Parse, stats);
+        let text = baseline.to_string();
+        assert_eq!(
+            text.lines().nth(1),
+            Some("2023/9 parse 1000000 2000000 3000000 4000000")
+        );
+        assert_eq!(
+            text.lines().nth(2),
+            Some("9 parse 1000000 2000000 3000000 4000000")
+        );
+        assert_eq!(Baseline::parse(&text), Ok(baseline));
+        assert_eq!(Baseline::parse("2023/x parse 1 2 3 4"), Err(1));
+        assert_eq!(Baseline::parse("9 parse 1 2 3"), Err(1));
+        assert_eq!(Baseline::parse("\n9 3 1 2 3 4"), Err(2));
+    }
+
+    #[test]
+    fn test_table() {
+        let results = [(Phase::Parse, Stats::from_samples(&[ms(2)]))];
+        let mut baseline = Baseline::default();
+        baseline.insert(2024, 9, Phase::Parse, Stats::from_samples(&[ms(4)]));
+        let text = table(2024, 9, &results, Some(&baseline));
+        assert!(text.lines().next().unwrap().ends_with("baseline   change"));
+        assert!(text.lines().nth(1).unwrap().ends_with("4.00ms   -50.0%"));
+        let text = table(2024, 10, &results, Some(&baseline));
+        assert!(text.lines().nth(1).unwrap().ends_with("-        -"));
+        let text = table(2023, 9, &results, Some(&baseline));
+        assert!(text.lines().nth(1).unwrap().ends_with("-        -"));
+    }
+}
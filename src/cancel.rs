@@ -0,0 +1,116 @@
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// Tells a solver to give up, because it is taking too long or was
+/// cancelled. Solvers don't get it: they ask `requested` now and then in
+/// their longest loops, and return whatever they have when it says so.
+#[derive(Debug, Clone, Default)]
+pub struct Cancel {
+    deadline: Option<Instant>,
+    cancelled: Arc<AtomicBool>,
+    /// `requested` said so, so the solver gave up
+    heeded: Arc<AtomicBool>,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Cancel>> = const { RefCell::new(None) };
+}
+
+impl Cancel {
+    /// Cancelled once `timeout` has passed, or never if `None`.
+    pub fn after(timeout: Option<Duration>) -> Self {
+        Cancel {
+            deadline: timeout.map(|t| Instant::now() + t),
+            ..Self::default()
+        }
+    }
+
+    /// Cancels it, for every clone of it.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed) || self.deadline.is_some_and(|d| Instant::now() >= d)
+    }
+
+    /// Whether a solver was told to give up, by `requested`. One that
+    /// finished before asking has a good answer, even if late.
+    pub fn heeded(&self) -> bool {
+        self.heeded.load(Ordering::Relaxed)
+    }
+
+    /// Calls `f` with this as what `requested` looks at in this thread,
+    /// until it returns or panics.
+    pub fn run<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _outer = Restore(CURRENT.replace(Some(self.clone())));
+        f()
+    }
+}
+
+/// Puts back the token of an outer `run` when dropped.
+struct Restore(Option<Cancel>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT.set(self.0.take());
+    }
+}
+
+/// Whether the solver running in this thread should give up.
+pub fn requested() -> bool {
+    CURRENT.with_borrow(|c| {
+        c.as_ref().is_some_and(|c| {
+            let cancelled = c.is_cancelled();
+            if cancelled {
+                c.heeded.store(true, Ordering::Relaxed);
+            }
+            cancelled
+        })
+    })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::{panic, time::Duration};
+
+    use super::{requested, Cancel};
+
+    #[test]
+    fn test_cancel() {
+        assert!(!requested());
+        let token = Cancel::after(None);
+        assert!(!token.run(requested));
+        let other = token.clone();
+        token.run(|| {
+            other.cancel();
+            assert!(requested());
+            // an inner one doesn't see the outer one
+            assert!(!Cancel::default().run(requested));
+            assert!(requested());
+        });
+        assert!(!requested());
+        assert!(token.is_cancelled() && token.heeded());
+
+        // cancelled, but done before asking
+        let late = Cancel::default();
+        late.run(|| late.cancel());
+        assert!(late.is_cancelled() && !late.heeded());
+
+        let token = Cancel::default();
+        token.cancel();
+        assert!(panic::catch_unwind(|| token.run(|| panic!("solver bug"))).is_err());
+        assert!(!requested());
+
+        let token = Cancel::after(Some(Duration::from_millis(5)));
+        assert!(!token.is_cancelled());
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(token.run(requested));
+    }
+}
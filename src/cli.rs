@@ -0,0 +1,858 @@
+use std::{fmt, path::PathBuf, time::Duration};
+
+use aoc_2024::{
+    answers, bench, input::Source, part::Part, report::Format, solution::DEFAULT_YEAR,
+    visual::Target,
+};
+
+pub const USAGE: &str = "\
+Usage:
+    aoc_2024 run <DAY> [--year Y] [--part 1|2] [--input PATH|-] [--format FORMAT]
+                 [--param NAME=VALUE]... [--alloc-stats] [--timeout SECS]
+                 [--visualize ansi|DIR]
+    aoc_2024 run <FIRST>..<LAST> [--year Y] [--part 1|2] [--input DIR]
+                 [--format FORMAT] [--threads N] [--alloc-stats] [--timeout SECS]
+                 [--visualize DIR]
+    aoc_2024 run --all [--year Y] [--part 1|2] [--input DIR] [--format FORMAT]
+                 [--threads N] [--alloc-stats] [--timeout SECS] [--visualize DIR]
+    aoc_2024 check [<DAYS>|--all] [--year Y] [--input DIR] [--answers PATH]
+                   [--update] [--format FORMAT] [--threads N] [--timeout SECS]
+    aoc_2024 bench <DAY> [--year Y] [--part 1|2] [--input PATH|-] [--iterations K]
+                   [--save PATH] [--compare PATH] [--param NAME=VALUE]...
+    aoc_2024 lint <DAY> [--year Y] [--input PATH|-]
+    aoc_2024 list [--year Y]
+    aoc_2024 new <DAY> [--year Y]
+    aoc_2024 --help
+
+Commands:
+    run     solve one day, a range of days, or every day with --all.
+            Ranges are inclusive with `..=` and exclusive with `..`, as in
+            Rust. When solving more than one day, a table with the time
+            taken by each one is printed at the end.
+    check   solve the given days (default: all of them) and compare their
+            answers with the recorded ones, failing if any differs
+    bench   parse and solve a day repeatedly, after warming up, and show
+            the min, median, mean and standard deviation of the times
+    lint    check that the input of a day has the shape it expects, such as
+            rectangular grids with a single start, and show every line
+            that doesn't, then whether the day can parse it
+    list    list the days, whether their input is available and their
+            parameters with their default values
+    new     add a day: its module with stubs to fill in, registered, and
+            an example in fixtures/day_N, or fixtures/Y/day_N for other
+            years than 2024. Run it from the crate root. It never
+            overwrites anything
+
+Options:
+    --part 1|2        only solve this part (default: both)
+    --input PATH|-    read the input from a file, a directory holding day_N
+                      files, or stdin with `-` (default: $AOC_INPUT_DIR/day_N
+                      or input/day_N, and Y/day_N in them for other years
+                      than 2024)
+    --answers PATH    file with the recorded answers (default: answers.toml)
+    --update          record the answers that are missing or differ
+    --format FORMAT   text (default), or json or csv with one record per part:
+                      year, day, part, answer, answer type, duration and
+                      status
+    --param NAME=VALUE
+                      change a parameter of the day, like the size of the
+                      grid, to solve the examples. Can be given many times
+    --alloc-stats     count the allocations of each step, the bytes allocated
+                      and the most allocated at once, and show them in a table
+                      or in the records
+    --timeout SECS    give up on a part after this many seconds, which may
+                      have decimals, and report it as timed out. Solvers stop
+                      when they next check, so it may take a little longer,
+                      and days that don't check run to the end
+    --visualize ansi|DIR
+                      draw what days 6, 14, 15, 16, 18 and 20 do, as frames
+                      in the terminal with `ansi`, or in files under
+                      DIR/day_N/part_P. Best with the examples
+    --threads N       solve up to N days at the same time (default: 1). Results
+                      are still shown in day order
+    --iterations K    times to run each part when benchmarking (default: 10)
+    --save PATH       keep the benchmark results in this file, along with
+                      those of other days already there
+    --compare PATH    compare the benchmark with the results saved in PATH
+    --year Y          the year of the days (default: 2024). With --all, or
+                      check and list without days, only that year; otherwise
+                      every year there is
+    -h, --help        print this message
+
+`aoc_2024 <DAY>` is short for `aoc_2024 run <DAY>`.";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Days {
+    One(usize),
+    /// first and last day, both included
+    Range(usize, usize),
+    All,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run {
+        /// `None` for the default year, or every year with `Days::All`
+        year: Option<usize>,
+        days: Days,
+        part: Option<Part>,
+        source: Source,
+        format: Format,
+        threads: usize,
+        /// values for the day's parameters, by name
+        params: Vec<(String, usize)>,
+        /// count what each step allocates
+        alloc_stats: bool,
+        /// for each part
+        timeout: Option<Duration>,
+        visualize: Option<Target>,
+    },
+    Check {
+        year: Option<usize>,
+        days: Days,
+        source: Source,
+        answers: PathBuf,
+        update: bool,
+        format: Format,
+        threads: usize,
+        timeout: Option<Duration>,
+    },
+    Bench {
+        year: Option<usize>,
+        day: usize,
+        part: Option<Part>,
+        source: Source,
+        iterations: usize,
+        /// where to keep the results, for later comparison
+        save: Option<PathBuf>,
+        /// earlier results to compare with
+        compare: Option<PathBuf>,
+        params: Vec<(String, usize)>,
+    },
+    New {
+        year: usize,
+        day: usize,
+    },
+    Lint {
+        year: Option<usize>,
+        day: usize,
+        source: Source,
+    },
+    List {
+        year: Option<usize>,
+    },
+    Help,
+}
+
+/// A command line we can't make sense of.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CliError(pub String);
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// Days in an event, whatever the registry has.
+pub const DAYS_IN_EVENT: usize = 25;
+
+fn error<T>(msg: impl Into<String>) -> Result<T, CliError> {
+    Err(CliError(msg.into()))
+}
+
+pub fn parse_day(arg: &str, num_days: usize) -> Result<usize, CliError> {
+    match arg.parse::<usize>() {
+        Ok(day) if 1 <= day && day <= num_days => Ok(day),
+        Ok(day) => error(format!("no day {day}. Days range from 1 to {num_days}.")),
+        Err(_) => error(format!(
+            "can't understand day `{arg}`. Use a number from 1 to {num_days}."
+        )),
+    }
+}
+
+/// `N`, `A..B` or `A..=B`.
+fn parse_days(arg: &str, num_days: usize) -> Result<Days, CliError> {
+    let Some((first, last)) = arg.split_once("..") else {
+        return Ok(Days::One(parse_day(arg, num_days)?));
+    };
+    let first = parse_day(first, num_days)?;
+    let last = match last.strip_prefix('=') {
+        Some(last) => parse_day(last, num_days)?,
+        None => match last.parse::<usize>() {
+            Ok(end) if 1 < end && end <= num_days + 1 => end - 1,
+            _ => return error(format!("can't understand the end of range `{arg}`.")),
+        },
+    };
+    if first > last {
+        return error(format!("range `{arg}` has no days."));
+    }
+    Ok(Days::Range(first, last))
+}
+
+/// Parses the arguments, without the program name.
+pub fn parse(args: &[String], num_days: usize) -> Result<Command, CliError> {
+    let Some(first) = args.first() else {
+        return error("missing command.");
+    };
+    match first.as_str() {
+        "-h" | "--help" | "help" => Ok(Command::Help),
+        "list" => parse_list(&args[1..], num_days),
+        "run" => parse_run(&args[1..], num_days),
+        "check" => parse_check(&args[1..], num_days),
+        "bench" => parse_bench(&args[1..], num_days),
+        "new" => parse_new(&args[1..]),
+        "lint" => parse_lint(&args[1..], num_days),
+        _ if first.chars().all(|c| c.is_ascii_digit()) => parse_run(args, num_days),
+        _ => error(format!("unknown command `{first}`.")),
+    }
+}
+
+/// Everything that may follow a command, before checking that it makes
+/// sense for it.
+#[derive(Default)]
+struct Options {
+    help: bool,
+    days: Option<Days>,
+    part: Option<Part>,
+    source: Option<Source>,
+    answers: Option<PathBuf>,
+    update: bool,
+    alloc_stats: bool,
+    format: Option<Format>,
+    iterations: Option<usize>,
+    threads: Option<usize>,
+    timeout: Option<Duration>,
+    visualize: Option<Target>,
+    save: Option<PathBuf>,
+    compare: Option<PathBuf>,
+    params: Vec<(String, usize)>,
+    year: Option<usize>,
+}
+
+/// Parses the options in `allowed`, plus `--help` and the days.
+fn parse_options(
+    command: &str,
+    args: &[String],
+    num_days: usize,
+    allowed: &[&str],
+) -> Result<Options, CliError> {
+    let mut opts = Options::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => opts.help = true,
+            option if option.starts_with('-') && !allowed.contains(&option) => {
+                return error(format!("unknown option `{arg}` for {command}."))
+            }
+            "--all" if opts.days.is_none() => opts.days = Some(Days::All),
+            "--all" => return error("give either days or --all."),
+            "--update" if !opts.update => opts.update = true,
+            "--alloc-stats" if !opts.alloc_stats => opts.alloc_stats = true,
+            "--part" if opts.part.is_none() => {
+                let value = value_of(arg, args.next())?;
+                opts.part = Some(value.parse().map_err(CliError)?);
+            }
+            "--input" if opts.source.is_none() => {
+                opts.source = Some(Source::from_arg(value_of(arg, args.next())?));
+            }
+            "--answers" if opts.answers.is_none() => {
+                opts.answers = Some(PathBuf::from(value_of(arg, args.next())?));
+            }
+            "--format" if opts.format.is_none() => {
+                let value = value_of(arg, args.next())?;
+                opts.format = Some(value.parse().map_err(CliError)?);
+            }
+            "--iterations" if opts.iterations.is_none() => {
+                let value = value_of(arg, args.next())?;
+                match value.parse() {
+                    Ok(n) if n > 0 => opts.iterations = Some(n),
+                    _ => return error("--iterations must be a positive number."),
+                }
+            }
+            "--threads" if opts.threads.is_none() => {
+                let value = value_of(arg, args.next())?;
+                match value.parse() {
+                    Ok(n) if n > 0 => opts.threads = Some(n),
+                    _ => return error("--threads must be a positive number."),
+                }
+            }
+            "--timeout" if opts.timeout.is_none() => {
+                let value = value_of(arg, args.next())?;
+                match value.parse::<f64>() {
+                    Ok(secs) if secs > 0. && secs.is_finite() => {
+                        opts.timeout = Some(Duration::from_secs_f64(secs))
+                    }
+                    _ => return error("--timeout must be a positive number of seconds."),
+                }
+            }
+            "--visualize" if opts.visualize.is_none() => {
+                opts.visualize = Some(Target::from_arg(value_of(arg, args.next())?));
+            }
+            "--param" => {
+                let value = value_of(arg, args.next())?;
+                let param = value
+                    .split_once('=')
+                    .and_then(|(name, n)| Some((name.to_string(), n.parse().ok()?)));
+                match param {
+                    Some(param) => opts.params.push(param),
+                    None => {
+                        return error(format!(
+                            "can't understand --param `{value}`. Use NAME=NUMBER."
+                        ))
+                    }
+                }
+            }
+            "--year" if opts.year.is_none() => {
+                let value = value_of(arg, args.next())?;
+                match value.parse() {
+                    Ok(year) if year >= 2015 => opts.year = Some(year),
+                    _ => return error(format!("can't understand year `{value}`.")),
+                }
+            }
+            "--save" if opts.save.is_none() => {
+                opts.save = Some(PathBuf::from(value_of(arg, args.next())?));
+            }
+            "--compare" if opts.compare.is_none() => {
+                opts.compare = Some(PathBuf::from(value_of(arg, args.next())?));
+            }
+            "--update" | "--alloc-stats" | "--part" | "--input" | "--answers" | "--format"
+            | "--iterations" | "--save" | "--compare" | "--year" | "--timeout" | "--visualize"
+            | "--threads" => return error(format!("{arg} given twice.")),
+            _ if opts.days.is_none() => opts.days = Some(parse_days(arg, num_days)?),
+            _ => return error(format!("unexpected argument `{arg}`.")),
+        }
+    }
+    if let Some(days) = &opts.days {
+        if !matches!(days, Days::One(_))
+            && matches!(opts.source, Some(Source::File(_) | Source::Stdin))
+        {
+            return error("with more than one day, --input must be a directory.");
+        }
+        if !matches!(days, Days::One(_)) && !opts.params.is_empty() {
+            return error("--param can only be given for a single day.");
+        }
+        if !matches!(days, Days::One(_)) && opts.visualize == Some(Target::Ansi) {
+            return error("with more than one day, --visualize must be a directory.");
+        }
+    }
+    Ok(opts)
+}
+
+fn parse_run(args: &[String], num_days: usize) -> Result<Command, CliError> {
+    let allowed = [
+        "--all",
+        "--part",
+        "--input",
+        "--format",
+        "--threads",
+        "--param",
+        "--year",
+        "--alloc-stats",
+        "--timeout",
+        "--visualize",
+    ];
+    let opts = parse_options("run", args, num_days, &allowed)?;
+    if opts.help {
+        return Ok(Command::Help);
+    }
+    let Some(days) = opts.days else {
+        return error("missing day to run. Give a day or --all.");
+    };
+    Ok(Command::Run {
+        year: opts.year,
+        days,
+        part: opts.part,
+        source: opts.source.unwrap_or_default(),
+        format: opts.format.unwrap_or_default(),
+        threads: opts.threads.unwrap_or(1),
+        params: opts.params,
+        alloc_stats: opts.alloc_stats,
+        timeout: opts.timeout,
+        visualize: opts.visualize,
+    })
+}
+
+fn parse_check(args: &[String], num_days: usize) -> Result<Command, CliError> {
+    let allowed = [
+        "--all",
+        "--input",
+        "--answers",
+        "--update",
+        "--format",
+        "--threads",
+        "--year",
+        "--timeout",
+    ];
+    let opts = parse_options("check", args, num_days, &allowed)?;
+    if opts.help {
+        return Ok(Command::Help);
+    }
+    Ok(Command::Check {
+        year: opts.year,
+        days: opts.days.unwrap_or(Days::All),
+        source: opts.source.unwrap_or_default(),
+        answers: opts
+            .answers
+            .unwrap_or_else(|| PathBuf::from(answers::DEFAULT_PATH)),
+        update: opts.update,
+        format: opts.format.unwrap_or_default(),
+        threads: opts.threads.unwrap_or(1),
+        timeout: opts.timeout,
+    })
+}
+
+fn parse_bench(args: &[String], num_days: usize) -> Result<Command, CliError> {
+    let allowed = [
+        "--part",
+        "--input",
+        "--iterations",
+        "--save",
+        "--compare",
+        "--param",
+        "--year",
+    ];
+    let opts = parse_options("bench", args, num_days, &allowed)?;
+    if opts.help {
+        return Ok(Command::Help);
+    }
+    let day = match opts.days {
+        Some(Days::One(day)) => day,
+        Some(_) => return error("bench takes a single day."),
+        None => return error("missing day to bench."),
+    };
+    Ok(Command::Bench {
+        year: opts.year,
+        day,
+        part: opts.part,
+        source: opts.source.unwrap_or_default(),
+        iterations: opts.iterations.unwrap_or(bench::DEFAULT_ITERATIONS),
+        save: opts.save,
+        compare: opts.compare,
+        params: opts.params,
+    })
+}
+
+fn parse_list(args: &[String], num_days: usize) -> Result<Command, CliError> {
+    let opts = parse_options("list", args, num_days, &["--year"])?;
+    if opts.help {
+        return Ok(Command::Help);
+    }
+    if opts.days.is_some() {
+        return error("list takes no days. Use --year to list only one year.");
+    }
+    Ok(Command::List { year: opts.year })
+}
+
+fn parse_new(args: &[String]) -> Result<Command, CliError> {
+    let opts = parse_options("new", args, DAYS_IN_EVENT, &["--year"])?;
+    if opts.help {
+        return Ok(Command::Help);
+    }
+    let day = match opts.days {
+        Some(Days::One(day)) => day,
+        Some(_) => return error("new takes a single day."),
+        None => return error("missing day to add."),
+    };
+    Ok(Command::New {
+        year: opts.year.unwrap_or(DEFAULT_YEAR),
+        day,
+    })
+}
+
+fn parse_lint(args: &[String], num_days: usize) -> Result<Command, CliError> {
+    let opts = parse_options("lint", args, num_days, &["--input", "--year"])?;
+    if opts.help {
+        return Ok(Command::Help);
+    }
+    let day = match opts.days {
+        Some(Days::One(day)) => day,
+        Some(_) => return error("lint takes a single day."),
+        None => return error("missing day to lint."),
+    };
+    Ok(Command::Lint {
+        year: opts.year,
+        day,
+        source: opts.source.unwrap_or_default(),
+    })
+}
+
+fn value_of<'a>(option: &str, value: Option<&'a String>) -> Result<&'a str, CliError> {
+    match value {
+        Some(value) => Ok(value),
+        None => error(format!("missing value for {option}.")),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::{path::PathBuf, time::Duration};
+
+    use super::{parse, CliError, Command, Days};
+    use aoc_2024::{input::Source, part::Part, report::Format, visual::Target};
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_run() {
+        assert_eq!(
+            parse(&args("run 3"), 25),
+            Ok(Command::Run {
+                year: None,
+                days: Days::One(3),
+                part: None,
+                source: Source::Default,
+                format: Format::Text,
+                threads: 1,
+                params: vec![],
+                alloc_stats: false,
+                timeout: None,
+                visualize: None
+            })
+        );
+        assert_eq!(parse(&args("run 3"), 25), parse(&args("3"), 25));
+        assert_eq!(
+            parse(&args("run 12 --part 2 --input -"), 25),
+            Ok(Command::Run {
+                year: None,
+                days: Days::One(12),
+                part: Some(Part::Two),
+                source: Source::Stdin,
+                format: Format::Text,
+                threads: 1,
+                params: vec![],
+                alloc_stats: false,
+                timeout: None,
+                visualize: None
+            })
+        );
+        assert_eq!(
+            parse(&args("run --input some/file 7"), 25),
+            Ok(Command::Run {
+                year: None,
+                days: Days::One(7),
+                part: None,
+                source: Source::File(PathBuf::from("some/file")),
+                format: Format::Text,
+                threads: 1,
+                params: vec![],
+                alloc_stats: false,
+                timeout: None,
+                visualize: None
+            })
+        );
+        assert_eq!(
+            parse(&args("run --all --part 1"), 25),
+            Ok(Command::Run {
+                year: None,
+                days: Days::All,
+                part: Some(Part::One),
+                source: Source::Default,
+                format: Format::Text,
+                threads: 1,
+                params: vec![],
+                alloc_stats: false,
+                timeout: None,
+                visualize: None
+            })
+        );
+    }
+
+    #[test]
+    fn test_params() {
+        assert_eq!(
+            parse(&args("run 14 --param width=11 --param height=7"), 25),
+            Ok(Command::Run {
+                year: None,
+                days: Days::One(14),
+                part: None,
+                source: Source::Default,
+                format: Format::Text,
+                threads: 1,
+                params: vec![("width".to_string(), 11), ("height".to_string(), 7)],
+                alloc_stats: false,
+                timeout: None,
+                visualize: None
+            })
+        );
+        assert!(matches!(
+            parse(&args("bench 11 --param blinks1=6"), 25),
+            Ok(Command::Bench { params, .. }) if params == vec![("blinks1".to_string(), 6)]
+        ));
+    }
+
+    #[test]
+    fn test_ranges() {
+        let days = |line: &str| match parse(&args(line), 25) {
+            Ok(Command::Run { days, .. }) => Ok(days),
+            Ok(other) => panic!("{other:?} is not run"),
+            Err(e) => Err(e),
+        };
+        assert_eq!(days("run 5..12"), Ok(Days::Range(5, 11)));
+        assert_eq!(days("run 5..=12"), Ok(Days::Range(5, 12)));
+        assert_eq!(days("run 1..26"), Ok(Days::Range(1, 25)));
+        assert_eq!(days("run 3..=3"), Ok(Days::Range(3, 3)));
+        assert!(days("run 3..3").is_err());
+        assert!(days("run 5..=26").is_err());
+        assert!(days("run 5..27").is_err());
+        assert!(days("run 12..5").is_err());
+        assert!(days("run 0..5").is_err());
+        assert!(days("run 5..").is_err());
+        assert!(days("run 5..12 --input -").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(
+            parse(&args("check"), 25),
+            Ok(Command::Check {
+                year: None,
+                days: Days::All,
+                source: Source::Default,
+                answers: PathBuf::from("answers.toml"),
+                update: false,
+                format: Format::Text,
+                threads: 1,
+                timeout: None
+            })
+        );
+        assert_eq!(
+            parse(&args("check 4..=6 --update --answers other.toml"), 25),
+            Ok(Command::Check {
+                year: None,
+                days: Days::Range(4, 6),
+                source: Source::Default,
+                answers: PathBuf::from("other.toml"),
+                update: true,
+                format: Format::Text,
+                threads: 1,
+                timeout: None
+            })
+        );
+        assert_eq!(parse(&args("check"), 25), parse(&args("check --all"), 25));
+        assert!(matches!(
+            parse(&args("check --threads 8"), 25),
+            Ok(Command::Check { threads: 8, .. })
+        ));
+        assert!(matches!(
+            parse(&args("check --format csv"), 25),
+            Ok(Command::Check {
+                format: Format::Csv,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_bench() {
+        assert_eq!(
+            parse(&args("bench 16"), 25),
+            Ok(Command::Bench {
+                year: None,
+                day: 16,
+                part: None,
+                source: Source::Default,
+                iterations: 10,
+                save: None,
+                compare: None,
+                params: vec![]
+            })
+        );
+        assert_eq!(
+            parse(
+                &args("bench 16 --part 2 --iterations 3 --save b.txt --compare a.txt"),
+                25
+            ),
+            Ok(Command::Bench {
+                year: None,
+                day: 16,
+                part: Some(Part::Two),
+                source: Source::Default,
+                iterations: 3,
+                save: Some(PathBuf::from("b.txt")),
+                compare: Some(PathBuf::from("a.txt")),
+                params: vec![]
+            })
+        );
+        assert!(parse(&args("bench"), 25).is_err());
+        assert!(parse(&args("bench 3..5"), 25).is_err());
+        assert!(parse(&args("bench --all"), 25).is_err());
+        assert!(parse(&args("bench 3 --iterations 0"), 25).is_err());
+        assert!(parse(&args("bench 3 --update"), 25).is_err());
+        assert!(parse(&args("run 3 --iterations 4"), 25).is_err());
+        assert!(parse(&args("run 3..5 --param width=3"), 25).is_err());
+        assert!(parse(&args("run 3 --param width"), 25).is_err());
+        assert!(parse(&args("run 3 --param width=-3"), 25).is_err());
+        assert!(parse(&args("check --param width=3"), 25).is_err());
+        assert!(parse(&args("bench 3 --threads 4"), 25).is_err());
+        assert!(parse(&args("run --all --threads 0"), 25).is_err());
+        assert_eq!(
+            parse(&args("run --all --threads 2 --threads 3"), 25),
+            Err(CliError("--threads given twice.".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(parse(&args(""), 25).is_err());
+        assert!(parse(&args("fly"), 25).is_err());
+        assert!(parse(&args("run"), 25).is_err());
+        assert_eq!(
+            parse(&args("run 26"), 25),
+            Err(CliError("no day 26. Days range from 1 to 25.".to_string()))
+        );
+        assert!(parse(&args("run 0"), 25).is_err());
+        assert!(parse(&args("run x"), 25).is_err());
+        assert!(parse(&args("run 3 4"), 25).is_err());
+        assert!(parse(&args("run 3 --all"), 25).is_err());
+        assert!(parse(&args("run 3 --part 3"), 25).is_err());
+        assert!(parse(&args("run 3 --part"), 25).is_err());
+        assert!(parse(&args("run 3 --part 1 --part 2"), 25).is_err());
+        assert!(parse(&args("run 3 --fast"), 25).is_err());
+        assert!(parse(&args("run --all --input -"), 25).is_err());
+        assert!(parse(&args("list 3"), 25).is_err());
+        assert!(parse(&args("run 3 --update"), 25).is_err());
+        assert!(parse(&args("check --part 1"), 25).is_err());
+        assert!(parse(&args("check --update --update"), 25).is_err());
+        assert!(parse(&args("run 3 --format xml"), 25).is_err());
+        assert!(parse(&args("check 3..5 --input -"), 25).is_err());
+    }
+
+    #[test]
+    fn test_other_commands() {
+        assert_eq!(parse(&args("list"), 25), Ok(Command::List { year: None }));
+        assert_eq!(
+            parse(&args("list --year 2023"), 25),
+            Ok(Command::List { year: Some(2023) })
+        );
+        assert_eq!(parse(&args("--help"), 25), Ok(Command::Help));
+        assert_eq!(parse(&args("-h"), 25), Ok(Command::Help));
+        assert_eq!(parse(&args("run 3 --help"), 25), Ok(Command::Help));
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(
+            parse(&args("new 3"), 3),
+            Ok(Command::New { year: 2024, day: 3 })
+        );
+        assert_eq!(
+            parse(&args("new --year 2025 12"), 3),
+            Ok(Command::New {
+                year: 2025,
+                day: 12
+            })
+        );
+        assert!(parse(&args("new 26"), 25).is_err());
+        assert!(parse(&args("new 1..3"), 25).is_err());
+        assert!(parse(&args("new 3 --year 24"), 25).is_err());
+        assert!(parse(&args("new 3 --part 1"), 25).is_err());
+        assert!(parse(&args("list --part 1"), 25).is_err());
+    }
+
+    #[test]
+    fn test_lint() {
+        assert_eq!(
+            parse(&args("lint 16 --input example.txt"), 25),
+            Ok(Command::Lint {
+                year: None,
+                day: 16,
+                source: Source::File(PathBuf::from("example.txt"))
+            })
+        );
+        assert!(parse(&args("lint"), 25).is_err());
+        assert!(parse(&args("lint 1..3"), 25).is_err());
+        assert!(parse(&args("lint 16 --part 1"), 25).is_err());
+    }
+
+    #[test]
+    fn test_year() {
+        assert!(matches!(
+            parse(&args("run 3 --year 2023"), 25),
+            Ok(Command::Run {
+                year: Some(2023),
+                days: Days::One(3),
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse(&args("check --year 2023"), 25),
+            Ok(Command::Check {
+                year: Some(2023),
+                days: Days::All,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse(&args("bench 3 --year 2023"), 25),
+            Ok(Command::Bench {
+                year: Some(2023),
+                ..
+            })
+        ));
+        assert!(parse(&args("run 3 --year 2023 --year 2022"), 25).is_err());
+        assert!(parse(&args("run 3 --year 23"), 25).is_err());
+    }
+
+    #[test]
+    fn test_alloc_stats() {
+        assert!(matches!(
+            parse(&args("run --all --alloc-stats --threads 4"), 25),
+            Ok(Command::Run {
+                alloc_stats: true,
+                threads: 4,
+                ..
+            })
+        ));
+        assert!(parse(&args("run 3 --alloc-stats --alloc-stats"), 25).is_err());
+        assert!(parse(&args("check --alloc-stats"), 25).is_err());
+    }
+
+    #[test]
+    fn test_timeout() {
+        assert!(matches!(
+            parse(&args("run --all --timeout 2.5"), 25),
+            Ok(Command::Run {
+                timeout: Some(t),
+                ..
+            }) if t == Duration::from_millis(2500)
+        ));
+        assert!(matches!(
+            parse(&args("check --timeout 10"), 25),
+            Ok(Command::Check {
+                timeout: Some(t),
+                ..
+            }) if t == Duration::from_secs(10)
+        ));
+        assert!(parse(&args("run 3 --timeout 0"), 25).is_err());
+        assert!(parse(&args("run 3 --timeout -1"), 25).is_err());
+        assert!(parse(&args("run 3 --timeout soon"), 25).is_err());
+        assert!(parse(&args("run 3 --timeout 1 --timeout 2"), 25).is_err());
+        assert!(parse(&args("bench 3 --timeout 1"), 25).is_err());
+    }
+
+    #[test]
+    fn test_visualize() {
+        assert!(matches!(
+            parse(&args("run 6 --visualize ansi"), 25),
+            Ok(Command::Run {
+                visualize: Some(Target::Ansi),
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse(&args("run --all --visualize frames"), 25),
+            Ok(Command::Run {
+                visualize: Some(Target::Dir(dir)),
+                ..
+            }) if dir.as_os_str() == "frames"
+        ));
+        assert!(parse(&args("run --all --visualize ansi"), 25).is_err());
+        assert!(parse(&args("run 6 --visualize ansi --visualize ansi"), 25).is_err());
+        assert!(parse(&args("check --visualize ansi"), 25).is_err());
+    }
+}
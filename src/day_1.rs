@@ -1,27 +1,35 @@
 use regex::Regex;
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
 
-fn parse_line(line: &str) -> (i32, i32) {
+use crate::{
+    answer::Answer,
+    parse::{lines, Line, ParseError},
+    solution::{Params, Solution},
+};
+
+type Columns = (Vec<i32>, Vec<i32>);
+
+fn parse_line(line: &Line) -> Result<(i32, i32), ParseError> {
     let re = Regex::new("^(\\d+)\\s+(\\d+)$").unwrap();
-    let numbers = re.captures(line).unwrap();
-    let n1: i32 = numbers.get(1).unwrap().as_str().parse().unwrap();
-    let n2: i32 = numbers.get(2).unwrap().as_str().parse().unwrap();
-    (n1, n2)
+    let numbers = line.captures(&re, "two numbers")?;
+    let n1: i32 = line.number(numbers.get(1).unwrap().as_str())?;
+    let n2: i32 = line.number(numbers.get(2).unwrap().as_str())?;
+    Ok((n1, n2))
 }
 
-fn split(lines: Vec<&str>) -> (Vec<i32>, Vec<i32>) {
+fn split(input: &str) -> Result<Columns, ParseError> {
     let mut col1: Vec<i32> = vec![];
     let mut col2: Vec<i32> = vec![];
-    for l in lines {
-        let (n1, n2) = parse_line(l);
+    for l in lines(input) {
+        let (n1, n2) = parse_line(&l)?;
         col1.push(n1);
         col2.push(n2);
     }
-    (col1, col2)
+    Ok((col1, col2))
 }
 
-fn do_prob_1(lines: Vec<&str>) -> i32 {
-    let (mut c1, mut c2) = split(lines);
+fn do_prob_1(columns: &Columns) -> i32 {
+    let (mut c1, mut c2) = columns.clone();
     c1.sort();
     c2.sort();
     c1.iter()
@@ -42,38 +50,59 @@ where
     ret
 }
 
-fn do_prob_2(lines: Vec<&str>) -> isize {
-    let (c1, c2) = split(lines);
-    let cc1 = counter(c1);
-    let cc2 = counter(c2);
+fn do_prob_2(columns: &Columns) -> isize {
+    let (c1, c2) = columns;
+    let cc1 = counter(c1.iter().copied());
+    let cc2 = counter(c2.iter().copied());
     cc1.iter()
         .map(|(k, v)| *k as isize * cc2.get(k).unwrap_or(&0) * v)
         .sum()
 }
 
-pub(crate) fn main() {
-    let input = fs::read_to_string("input/day_1").expect("no input file");
-    let input: Vec<&str> = input.trim().split("\n").collect();
-    println!("result: {}", do_prob_1(input.clone()));
-    println!("result: {}", do_prob_2(input.clone()));
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Columns;
+    const DAY: usize = 1;
+    const TITLE: &'static str = "Historian Hysteria";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        split(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        do_prob_1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        do_prob_2(input).into()
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
-    use super::{do_prob_1, do_prob_2};
+    use super::{do_prob_1, do_prob_2, split, Columns};
 
-    fn input() -> Vec<&'static str> {
-        vec!["3   4", "4   3", "2   5", "1   3", "3   9", "3   3"]
+    fn input() -> Columns {
+        split("3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n").unwrap()
     }
 
     #[test]
     fn test() {
-        let r = do_prob_1(input());
+        let r = do_prob_1(&input());
         assert_eq!(r, 11);
     }
 
     #[test]
     fn test_p2() {
-        assert_eq!(do_prob_2(input()), 31);
+        assert_eq!(do_prob_2(&input()), 31);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e = split("3   4\n4   x\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 1, "4   x"));
+        let e = split("3   99999999999\n").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 5));
     }
 }
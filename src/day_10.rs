@@ -1,11 +1,20 @@
 use std::collections::{HashMap, HashSet};
 
-struct MapGrid {
+use crate::{
+    answer::Answer,
+    lint::Constraint,
+    parse::{grid, lines, ParseError},
+    solution::{Params, Solution},
+};
+
+pub struct MapGrid {
     starts: Vec<usize>,
     nodes: HashMap<usize, Vec<usize>>,
 }
 impl MapGrid {
-    fn from(lines: &[&str]) -> Self {
+    /// Impassable tiles, which some examples have, are `.`.
+    fn from(input: &str) -> Result<Self, ParseError> {
+        let lines = grid(&lines(input), "0123456789.")?;
         let height = lines.len();
         let width = lines[0].len();
         let mut starts: Vec<usize> = vec![];
@@ -31,7 +40,7 @@ impl MapGrid {
                 }
             }
         }
-        Self { starts, nodes }
+        Ok(Self { starts, nodes })
     }
 
     fn reachable_in(&self, start: usize, steps: usize) -> HashSet<usize> {
@@ -57,23 +66,37 @@ impl MapGrid {
     }
 }
 
-fn prob1(input: &[&'static str]) -> usize {
-    let g = MapGrid::from(input);
+fn prob1(g: &MapGrid) -> usize {
     g.starts.iter().map(|&s| g.reachable_in(s, 9).len()).sum()
 }
 
-fn prob2(input: &[&'static str]) -> usize {
-    let g = MapGrid::from(input);
+fn prob2(g: &MapGrid) -> usize {
     g.starts
         .iter()
         .map(|&s| g.reachable_counting_paths(s, 9).len())
         .sum()
 }
 
-pub(crate) fn main() {
-    let input: Vec<&str> = include_str!("../input/day_10").trim().split("\n").collect();
-    println!("prob1: {}", prob1(&input.clone()));
-    println!("prob2: {}", prob2(&input.clone()));
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = MapGrid;
+    const DAY: usize = 10;
+    const TITLE: &'static str = "Hoof It";
+    const CONSTRAINTS: &'static [Constraint] =
+        &[Constraint::Rectangular, Constraint::Chars("0123456789.")];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        MapGrid::from(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        prob2(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -82,23 +105,26 @@ pub mod tests {
 
     use super::{prob1, prob2, MapGrid};
 
-    fn input() -> Vec<&'static str> {
-        vec![
+    fn input() -> MapGrid {
+        let lines = [
             "89010123", "78121874", "87430965", "96549874", "45678903", "32019012", "01329801",
             "10456732",
-        ]
+        ];
+        MapGrid::from(&lines.join("\n")).unwrap()
     }
 
     #[test]
     fn test_parse() {
-        let g = MapGrid::from(&input());
+        let g = input();
         assert_eq!(g.starts.len(), 9);
         assert_eq!(g.nodes[&2], vec![3, 10]);
+        let e = MapGrid::from("0123\n12x4").err().unwrap();
+        assert_eq!((e.line, e.column), (2, 3));
     }
 
     #[test]
     fn test_from_to_steps() {
-        let g = MapGrid::from(&input());
+        let g = input();
         assert_eq!(g.reachable_in(2, 1), HashSet::from([3, 10]));
         assert_eq!(g.reachable_in(2, 9).len(), 5);
         assert_eq!(g.reachable_in(4, 9).len(), 6);
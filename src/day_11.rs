@@ -1,8 +1,13 @@
-use crate::utils::counter;
+use crate::{
+    answer::Answer,
+    parse::{single_line, ParseError},
+    solution::{Param, Params, Solution},
+    utils::counter,
+};
 use std::collections::HashMap;
 
-fn parse(input: &str) -> Vec<usize> {
-    input.split(" ").map(|n| n.parse().unwrap()).collect()
+fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    single_line(input)?.numbers(" ")
 }
 
 fn blink(n: usize) -> Vec<usize> {
@@ -10,7 +15,7 @@ fn blink(n: usize) -> Vec<usize> {
         return vec![1];
     }
     let digits = (n as f64).log(10.).floor() as u32 + 1;
-    if digits % 2 == 0 {
+    if digits.is_multiple_of(2) {
         let left = n / (10u32.pow(digits / 2) as usize);
         let right = n % (10u32.pow(digits / 2) as usize);
         return vec![left, right];
@@ -22,9 +27,8 @@ fn blink_on_vec(ns: Vec<usize>) -> Vec<usize> {
     ns.iter().flat_map(|&n| blink(n)).collect()
 }
 
-fn prob1(input: &str) -> usize {
-    let ns = parse(input);
-    blink_n_times(ns, 25)
+fn prob1(ns: &[usize], blinks: usize) -> usize {
+    blink_n_times(ns.to_vec(), blinks)
 }
 
 fn blink_n_times(mut ns: Vec<usize>, times: usize) -> usize {
@@ -48,23 +52,49 @@ fn blink_n_times_map(ns: Vec<usize>, times: usize) -> usize {
     nsc.values().sum()
 }
 
-fn prob2(input: &str) -> usize {
-    let ns = parse(input);
-    blink_n_times_map(ns, 75)
+fn prob2(ns: &[usize], blinks: usize) -> usize {
+    blink_n_times_map(ns.to_vec(), blinks)
 }
 
-pub(crate) fn main() {
-    let input = include_str!("../input/day_11").trim();
-    println!("prob1: {}", prob1(input));
-    println!("prob2: {}", prob2(input));
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<usize>;
+    const DAY: usize = 11;
+    const TITLE: &'static str = "Plutonian Pebbles";
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "blinks1",
+            default: 25,
+            help: "times to blink in part 1",
+        },
+        Param {
+            name: "blinks2",
+            default: 75,
+            help: "times to blink in part 2",
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, params: &Params) -> Answer {
+        prob1(input, params.get("blinks1")).into()
+    }
+
+    fn part2(input: &Self::Input, params: &Params) -> Answer {
+        prob2(input, params.get("blinks2")).into()
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
-    use super::{blink, prob1, prob2};
+    use super::{blink, blink_n_times, blink_n_times_map, parse, prob1, prob2};
+    use crate::differential::{assert_agree, shrink_usize, shrink_vec, Rng};
 
-    fn input() -> &'static str {
-        "125 17"
+    fn input() -> Vec<usize> {
+        parse("125 17").unwrap()
     }
 
     #[test]
@@ -75,13 +105,54 @@ pub mod tests {
         assert_eq!(blink(99), vec![9, 9]);
     }
 
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("0 1 10 99 999\n"), Ok(vec![0, 1, 10, 99, 999]));
+        assert_eq!(parse("125 -17").unwrap_err().column, 5);
+    }
+
     #[test]
     fn test_prob1() {
-        assert_eq!(prob1(input()), 55312);
+        assert_eq!(prob1(&input(), 25), 55312);
     }
 
     #[test]
     fn test_prob2() {
-        assert_eq!(prob2(input()), 65601038650482); // actually, not in the statement as example
+        assert_eq!(prob2(&input(), 75), 65601038650482); // actually, not in the statement as example
+    }
+
+    #[test]
+    fn test_blink_map() {
+        let generate = |rng: &mut Rng| {
+            let stones = rng.between(1, 4);
+            // small ones split often, so only a few have many digits
+            let ns: Vec<usize> = (0..stones)
+                .map(|_| match rng.one_in(4) {
+                    true => rng.between(0, 1_000_000_000),
+                    false => rng.between(0, 100),
+                })
+                .collect();
+            (ns, rng.between(0, 15))
+        };
+        let shrink = |(ns, times): &(Vec<usize>, usize)| {
+            let mut ret: Vec<(Vec<usize>, usize)> = shrink_usize(*times)
+                .into_iter()
+                .map(|t| (ns.clone(), t))
+                .collect();
+            ret.extend(
+                shrink_vec(ns, |&n| shrink_usize(n))
+                    .into_iter()
+                    .map(|v| (v, *times)),
+            );
+            ret
+        };
+        assert_agree(
+            "blink_n_times and blink_n_times_map",
+            200,
+            generate,
+            shrink,
+            |(ns, times)| blink_n_times(ns.clone(), *times),
+            |(ns, times)| blink_n_times_map(ns.clone(), *times),
+        );
     }
 }
@@ -1,6 +1,12 @@
 use std::collections::HashSet;
 
-use crate::utils::counter;
+use crate::{
+    answer::Answer,
+    lint::Constraint,
+    parse::{grid, lines, ParseError},
+    solution::{Params, Solution},
+    utils::counter,
+};
 
 #[derive(PartialEq, Eq, Hash, Clone, Debug, Copy)]
 enum Orientation {
@@ -226,24 +232,43 @@ fn regions(garden: &[Vec<char>]) -> Vec<Region> {
     regions
 }
 
-fn prob1(input: Vec<&str>) -> usize {
-    let rs = regions(&parse(&input));
+fn prob1(garden: &[Vec<char>]) -> usize {
+    let rs = regions(garden);
     rs.iter().map(|r| r.price()).sum()
 }
 
-fn prob2(input: Vec<&str>) -> usize {
-    let rs = regions(&parse(&input));
+fn prob2(garden: &[Vec<char>]) -> usize {
+    let rs = regions(garden);
     rs.iter().map(|r| r.price_discount()).sum()
 }
 
-fn parse(input: &[&str]) -> Vec<Vec<char>> {
-    input.iter().map(|line| line.chars().collect()).collect()
+const PLANTS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+fn parse(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let rows = grid(&lines(input), PLANTS)?;
+    Ok(rows.iter().map(|line| line.chars().collect()).collect())
 }
 
-pub(crate) fn main() {
-    let input: Vec<&str> = include_str!("../input/day_12").trim().split("\n").collect();
-    println!("prob1: {}", prob1(input.clone()));
-    println!("prob2: {}", prob2(input.clone()));
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Vec<char>>;
+    const DAY: usize = 12;
+    const TITLE: &'static str = "Garden Groups";
+    const CONSTRAINTS: &'static [Constraint] =
+        &[Constraint::Rectangular, Constraint::Chars(PLANTS)];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        prob2(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -252,21 +277,25 @@ pub mod tests {
 
     use super::{parse, prob1, prob2, regions, Orientation, Region, Side};
 
-    fn input1() -> Vec<&'static str> {
-        vec!["AAAA", "BBCD", "BBCC", "EEEC"]
+    fn garden(lines: &[&str]) -> Vec<Vec<char>> {
+        parse(&lines.join("\n")).unwrap()
+    }
+
+    fn input1() -> Vec<Vec<char>> {
+        garden(&["AAAA", "BBCD", "BBCC", "EEEC"])
     }
-    fn input3() -> Vec<&'static str> {
-        vec!["OOOOO", "OXOXO", "OOOOO", "OXOXO", "OOOOO"]
+    fn input3() -> Vec<Vec<char>> {
+        garden(&["OOOOO", "OXOXO", "OOOOO", "OXOXO", "OOOOO"])
     }
-    fn input4() -> Vec<&'static str> {
-        vec!["EEEEE", "EXXXX", "EEEEE", "EXXXX", "EEEEE"]
+    fn input4() -> Vec<Vec<char>> {
+        garden(&["EEEEE", "EXXXX", "EEEEE", "EXXXX", "EEEEE"])
     }
-    fn input5() -> Vec<&'static str> {
-        vec!["AAAAAA", "AAABBA", "AAABBA", "ABBAAA", "ABBAAA", "AAAAAA"]
+    fn input5() -> Vec<Vec<char>> {
+        garden(&["AAAAAA", "AAABBA", "AAABBA", "ABBAAA", "ABBAAA", "AAAAAA"])
     }
 
-    fn input2() -> Vec<&'static str> {
-        vec![
+    fn input2() -> Vec<Vec<char>> {
+        garden(&[
             "RRRRIICCFF",
             "RRRRIICCCF",
             "VVRRRCCFFF",
@@ -277,12 +306,12 @@ pub mod tests {
             "MIIIIIJJEE",
             "MIIISIJEEE",
             "MMMISSJEEE",
-        ]
+        ])
     }
 
     #[test]
     fn test_from_plot() {
-        let g = parse(&input1());
+        let g = input1();
         let r = Region::from_plot(&g.clone(), (0, 0));
         assert_eq!(r._plant, 'A');
         assert_eq!(r.plots.len(), 4);
@@ -293,17 +322,17 @@ pub mod tests {
 
     #[test]
     fn test_regions() {
-        let g = parse(&input1());
+        let g = input1();
         let rs = regions(&g);
         assert_eq!(rs.len(), 5);
         assert_eq!(
             rs.iter().map(|r| r._plant).collect::<HashSet<char>>(),
             "ABCDE".chars().collect::<HashSet<char>>()
         );
-        let g = parse(&input2());
+        let g = input2();
         let rs = regions(&g);
         assert_eq!(rs.len(), 11);
-        let g = parse(&input3());
+        let g = input3();
         let rs = regions(&g);
         assert_eq!(rs.len(), 5);
         assert_eq!(
@@ -314,7 +343,7 @@ pub mod tests {
 
     #[test]
     fn test_price() {
-        let g = parse(&input2());
+        let g = input2();
         let r = Region::from_plot(&g, (0, 0));
         assert_eq!(r.area(), 12);
         assert_eq!(r.perimeter(), 18);
@@ -324,11 +353,17 @@ pub mod tests {
     #[test]
     fn test_prob1() {
         let g = input1();
-        assert_eq!(prob1(g), 140);
+        assert_eq!(prob1(&g), 140);
         let g = input2();
-        assert_eq!(prob1(g), 1930);
+        assert_eq!(prob1(&g), 1930);
         let g = input3();
-        assert_eq!(prob1(g), 772);
+        assert_eq!(prob1(&g), 772);
+    }
+
+    #[test]
+    fn test_parse() {
+        let e = parse("AAAA\nBBcD").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 3, "c"));
     }
 
     #[test]
@@ -361,9 +396,9 @@ pub mod tests {
 
     #[test]
     fn test_prob2() {
-        assert_eq!(prob2(input1()), 80);
-        assert_eq!(prob2(input2()), 1206);
-        assert_eq!(prob2(input4()), 236);
-        assert_eq!(prob2(input5()), 368);
+        assert_eq!(prob2(&input1()), 80);
+        assert_eq!(prob2(&input2()), 1206);
+        assert_eq!(prob2(&input4()), 236);
+        assert_eq!(prob2(&input5()), 368);
     }
 }
@@ -1,31 +1,44 @@
 use regex::Regex;
 
+use crate::{
+    answer::Answer,
+    lint::Constraint,
+    parse::{sections, Line, ParseError},
+    solution::{Params, Solution},
+};
+
 #[derive(Debug)]
-struct Machine {
+pub struct Machine {
     a: (isize, isize),
     b: (isize, isize),
     prize: (isize, isize),
 }
 impl Machine {
-    fn from(lines: &[&str]) -> Self {
+    fn from(lines: &[Line]) -> Result<Self, ParseError> {
         let butreg = Regex::new("^Button (A|B): X\\+(\\d+), Y\\+(\\d+)").unwrap();
         let prizereg = Regex::new("^Prize: X=(\\d+), Y=(\\d+)").unwrap();
-        let buttona = butreg.captures(lines[0]).unwrap();
+        let [la, lb, lp] = lines else {
+            return Err(match lines.get(3) {
+                Some(extra) => extra.error("a blank line between machines"),
+                None => ParseError::missing(lines, "three lines per machine"),
+            });
+        };
+        let buttona = la.captures(&butreg, "a button")?;
         let a: (isize, isize) = (
-            buttona.get(2).unwrap().as_str().parse().unwrap(),
-            buttona.get(3).unwrap().as_str().parse().unwrap(),
+            la.number(buttona.get(2).unwrap().as_str())?,
+            la.number(buttona.get(3).unwrap().as_str())?,
         );
-        let buttonb = butreg.captures(lines[1]).unwrap();
+        let buttonb = lb.captures(&butreg, "a button")?;
         let b: (isize, isize) = (
-            buttonb.get(2).unwrap().as_str().parse().unwrap(),
-            buttonb.get(3).unwrap().as_str().parse().unwrap(),
+            lb.number(buttonb.get(2).unwrap().as_str())?,
+            lb.number(buttonb.get(3).unwrap().as_str())?,
         );
-        let prizec = prizereg.captures(lines[2]).unwrap();
+        let prizec = lp.captures(&prizereg, "a prize")?;
         let prize: (isize, isize) = (
-            prizec.get(1).unwrap().as_str().parse().unwrap(),
-            prizec.get(2).unwrap().as_str().parse().unwrap(),
+            lp.number(prizec.get(1).unwrap().as_str())?,
+            lp.number(prizec.get(2).unwrap().as_str())?,
         );
-        Self { a, b, prize }
+        Ok(Self { a, b, prize })
     }
 
     fn det(&self) -> isize {
@@ -69,30 +82,40 @@ impl Machine {
     }
 }
 
-fn parse(input: &[&str]) -> Vec<Machine> {
-    (0..input.len())
-        .step_by(4)
-        .map(|i| Machine::from(&input[i..(i + 3)]))
-        .collect()
+fn parse(input: &str) -> Result<Vec<Machine>, ParseError> {
+    sections(input).iter().map(|s| Machine::from(s)).collect()
 }
 
-fn prob1(input: &[&str]) -> isize {
-    let machines = parse(input);
+fn prob1(machines: &[Machine]) -> isize {
     machines.iter().filter_map(|m| m.tokens()).sum()
 }
 
-fn prob2(input: &[&str]) -> isize {
-    let machines = parse(input);
+fn prob2(machines: &[Machine]) -> isize {
     machines
         .iter()
         .filter_map(|m| m.machine_prob_2().tokens())
         .sum()
 }
 
-pub(crate) fn main() {
-    let input: Vec<&str> = include_str!("../input/day_13").trim().split("\n").collect();
-    println!("prob1: {}", prob1(&input.clone()));
-    println!("prob2: {}", prob2(&input.clone()));
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Machine>;
+    const DAY: usize = 13;
+    const TITLE: &'static str = "Claw Contraption";
+    const CONSTRAINTS: &'static [Constraint] = &[Constraint::BlockLines(3)];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        prob2(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -119,18 +142,29 @@ pub mod tests {
         ]
     }
 
+    fn machines() -> Vec<Machine> {
+        parse(&input().join("\n")).unwrap()
+    }
+
     #[test]
     fn test_parse() {
-        let p = parse(&input());
+        let p = machines();
         assert_eq!(p.len(), 4);
         assert_eq!(p[0].a, (94, 34));
         assert_eq!(p[0].b, (22, 67));
         assert_eq!(p[0].prize, (8400, 5400));
+
+        let mut lines = input();
+        lines[5] = "Button B: X-67, Y+21";
+        let e = parse(&lines.join("\n")).unwrap_err();
+        assert_eq!((e.line, e.expected.as_str()), (6, "a button"));
+        let e = parse(&input()[..6].join("\n")).unwrap_err();
+        assert_eq!(e.line, 7);
     }
 
     #[test]
     fn test_solve() {
-        let p = parse(&input());
+        let p = machines();
         assert_eq!(p[0].solve(), Some((80, 40)));
         assert_eq!(p[0].tokens(), Some(280));
         assert_eq!(p[1].solve(), None);
@@ -141,12 +175,12 @@ pub mod tests {
 
     #[test]
     fn test_prob1() {
-        assert_eq!(prob1(&input()), 480);
+        assert_eq!(prob1(&machines()), 480);
     }
 
     #[test]
     fn test_prob2() {
-        let p: Vec<Machine> = parse(&input()).iter().map(|m| m.machine_prob_2()).collect();
+        let p: Vec<Machine> = machines().iter().map(|m| m.machine_prob_2()).collect();
         assert_eq!(
             p.iter().map(|m| m.solve().is_some()).collect::<Vec<bool>>(),
             vec![false, true, false, true]
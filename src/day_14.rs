@@ -1,49 +1,46 @@
 use regex::Regex;
 
-use crate::utils::counter;
-
-struct PV {
-    pos: (isize, isize),
-    vel: (isize, isize),
+use crate::{
+    answer::Answer,
+    cancel,
+    parse::{lines, Line, ParseError},
+    solution::{Param, Params, Solution},
+    utils::{counter, IJ},
+    visual,
+};
+
+/// Position and velocity, with `i` the y and `j` the x of the puzzle.
+pub struct PV {
+    pos: IJ,
+    vel: IJ,
 }
 impl PV {
-    fn from(line: &str) -> Self {
+    fn from(line: &Line) -> Result<Self, ParseError> {
         let re = Regex::new("^p=([0-9-]+),([0-9-]+) v=([0-9-]+),([0-9-]+)$").unwrap();
-        let cap = re.captures(line).unwrap();
-        let pos: (isize, isize) = (
-            cap.get(1).unwrap().as_str().parse().unwrap(),
-            cap.get(2).unwrap().as_str().parse().unwrap(),
-        );
-        let vel: (isize, isize) = (
-            cap.get(3).unwrap().as_str().parse().unwrap(),
-            cap.get(4).unwrap().as_str().parse().unwrap(),
-        );
-        Self { pos, vel }
+        let cap = line.captures(&re, "a robot like `p=0,4 v=3,-3`")?;
+        let pos = IJ {
+            j: line.number(cap.get(1).unwrap().as_str())?,
+            i: line.number(cap.get(2).unwrap().as_str())?,
+        };
+        let vel = IJ {
+            j: line.number(cap.get(3).unwrap().as_str())?,
+            i: line.number(cap.get(4).unwrap().as_str())?,
+        };
+        Ok(Self { pos, vel })
     }
-    fn pos_after(&self, time: isize, width: isize, height: isize) -> (isize, isize) {
-        shift_into(
-            (
-                self.pos.0 + time * self.vel.0,
-                self.pos.1 + time * self.vel.1,
-            ),
-            width,
-            height,
-        )
+    fn pos_after(&self, time: isize, width: isize, height: isize) -> IJ {
+        (self.pos + time * self.vel).rem_euclid(IJ {
+            i: height,
+            j: width,
+        })
     }
 }
-fn parse(lines: &[&str]) -> Vec<PV> {
-    lines.iter().map(|l| PV::from(l)).collect()
-}
-fn shift_into(p: (isize, isize), width: isize, height: isize) -> (isize, isize) {
-    (
-        (p.0 % width + width) % width,
-        (p.1 % height + height) % height,
-    )
+fn parse(input: &str) -> Result<Vec<PV>, ParseError> {
+    lines(input).iter().map(PV::from).collect()
 }
 
-fn prob1(input: &[&str], width: isize, height: isize) -> usize {
-    let pvs = parse(input);
-    let pos_after: Vec<(isize, isize)> = pvs
+fn prob1(pvs: &[PV], width: isize, height: isize) -> usize {
+    let pos_after: Vec<IJ> = pvs
         .iter()
         .map(|pv| pv.pos_after(100, width, height))
         .collect();
@@ -51,9 +48,9 @@ fn prob1(input: &[&str], width: isize, height: isize) -> usize {
     quadrants.values().product()
 }
 
-fn quadrant(p: &(isize, isize), width: isize, height: isize) -> Option<isize> {
-    let mshor = (p.0 - (width - 1) / 2).signum();
-    let msver = (p.1 - (height - 1) / 2).signum();
+fn quadrant(p: &IJ, width: isize, height: isize) -> Option<isize> {
+    let mshor = (p.j - (width - 1) / 2).signum();
+    let msver = (p.i - (height - 1) / 2).signum();
     if mshor * msver != 0 {
         Some(mshor + 1 + (msver + 1) / 2)
     } else {
@@ -61,43 +58,76 @@ fn quadrant(p: &(isize, isize), width: isize, height: isize) -> Option<isize> {
     }
 }
 
-fn display(ps: &[(isize, isize)], width: isize, height: isize) {
+fn display(ps: &[IJ], width: isize, height: isize) -> String {
     let mut d: Vec<String> = vec![String::from_iter(vec![' '; width as usize]); height as usize];
     for p in ps {
-        d[p.1 as usize].replace_range((p.0 as usize)..(p.0 as usize + 1), "*");
+        d[p.i as usize].replace_range((p.j as usize)..(p.j as usize + 1), "*");
     }
-    println!("{}", String::from_iter(vec!['='; width as usize]));
-    d.iter().for_each(|l| println!("{l}"));
-    println!("{}", String::from_iter(vec!['='; width as usize]));
+    let border = String::from_iter(vec!['='; width as usize]);
+    format!("{border}\n{}\n{border}\n", d.join("\n"))
 }
 
-fn prob2(input: &[&str], width: isize, height: isize) -> isize {
+fn prob2(pvs: &[PV], width: isize, height: isize) -> isize {
     // This is just many attempts of trial and error
-    let pvs = parse(input);
     for t in 0..10000 {
-        let pos_after: Vec<(isize, isize)> = pvs
+        if cancel::requested() {
+            break;
+        }
+        let pos_after: Vec<IJ> = pvs
             .iter()
             .map(|pv| pv.pos_after(t, width, height))
             .collect();
 
-        let cps = counter(pos_after.iter().map(|(x, _)| x));
+        let cps = counter(pos_after.iter().map(|p| p.j));
         let mxh = *cps.values().max().unwrap();
 
-        let cps = counter(pos_after.iter().map(|(_, y)| y));
+        let cps = counter(pos_after.iter().map(|p| p.i));
         let myh = *cps.values().max().unwrap();
 
         if mxh > 23 && myh > 20 {
-            display(&pos_after, width, height);
+            visual::frame(|| display(&pos_after, width, height));
             return t;
         }
     }
     0
 }
 
-pub(crate) fn main() {
-    let input: Vec<&str> = include_str!("../input/day_14").trim().split("\n").collect();
-    println!("prob1: {}", prob1(&input.clone(), 101, 103));
-    println!("prob2: {}", prob2(&input.clone(), 101, 103));
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<PV>;
+    const DAY: usize = 14;
+    const TITLE: &'static str = "Restroom Redoubt";
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "width",
+            default: 101,
+            help: "width of the space the robots move in",
+        },
+        Param {
+            name: "height",
+            default: 103,
+            help: "height of the space the robots move in",
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn check(_input: &Self::Input, params: &Params) -> Result<(), String> {
+        params.positive(&["width", "height"])
+    }
+
+    fn part1(input: &Self::Input, params: &Params) -> Answer {
+        let (width, height) = (params.get("width"), params.get("height"));
+        prob1(input, width as isize, height as isize).into()
+    }
+
+    fn part2(input: &Self::Input, params: &Params) -> Answer {
+        let (width, height) = (params.get("width"), params.get("height"));
+        prob2(input, width as isize, height as isize).into()
+    }
 }
 
 #[cfg(test)]
@@ -105,6 +135,7 @@ pub mod tests {
     use crate::day_14::prob1;
 
     use super::{display, parse};
+    use crate::utils::IJ;
 
     fn input() -> Vec<&'static str> {
         vec![
@@ -125,25 +156,30 @@ pub mod tests {
 
     #[test]
     fn test_parse() {
-        let pvs = parse(&input());
+        let pvs = parse(&input().join("\n")).unwrap();
         assert_eq!(pvs.len(), 12);
-        assert_eq!(pvs[1].pos, (6, 3));
-        assert_eq!(pvs[1].vel, (-1, -3));
-        assert_eq!(pvs[2].pos, (10, 3));
-        assert_eq!(pvs[2].vel, (-1, 2));
+        assert_eq!(pvs[1].pos, IJ { i: 3, j: 6 });
+        assert_eq!(pvs[1].vel, IJ { i: -3, j: -1 });
+        assert_eq!(pvs[2].pos, IJ { i: 3, j: 10 });
+        assert_eq!(pvs[2].vel, IJ { i: 2, j: -1 });
+        let e = parse("p=0,4 v=3,-3\np=6,3 v=-1,3-").err().unwrap();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 12, "3-"));
     }
 
     #[test]
     fn test_prob1() {
-        assert_eq!(prob1(&input(), 11, 7), 12);
+        assert_eq!(prob1(&parse(&input().join("\n")).unwrap(), 11, 7), 12);
     }
 
     #[test]
     fn test_prob2() {
-        let pvs = parse(&input());
+        let pvs = parse(&input().join("\n")).unwrap();
         for t in 0..40 {
-            let ps: Vec<(isize, isize)> = pvs.iter().map(|pv| pv.pos_after(t, 11, 7)).collect();
-            display(&ps, 11, 7);
+            let ps: Vec<IJ> = pvs.iter().map(|pv| pv.pos_after(t, 11, 7)).collect();
+            let text = display(&ps, 11, 7);
+            assert_eq!(text.lines().count(), 9);
+            assert!(text.lines().all(|l| l.len() == 11));
+            assert!(text.matches('*').count() <= pvs.len());
         }
     }
 }
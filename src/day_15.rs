@@ -1,95 +1,94 @@
 use std::collections::HashSet;
 
-struct Maze {
+use crate::{
+    answer::Answer,
+    lint::Constraint,
+    parse::{grid, lines, only_one, sections, ParseError},
+    solution::{Params, Solution},
+    utils::{Direction, IJ},
+    visual,
+};
+
+#[derive(Clone)]
+pub struct Maze {
     height: isize,
     width: isize,
-    boxes: HashSet<(isize, isize)>,
-    rocks: HashSet<(isize, isize)>,
-    instructions: Vec<char>,
-    position: (isize, isize),
+    boxes: HashSet<IJ>,
+    rocks: HashSet<IJ>,
+    instructions: Vec<Direction>,
+    position: IJ,
 }
 
 impl Maze {
-    fn from(lines: &[&str]) -> Self {
-        let width = lines[0].len() as isize;
-        let (height, _) = lines
-            .iter()
-            .enumerate()
-            .find(|(_, &l)| l.is_empty())
-            .unwrap();
-        let mut boxes: HashSet<(isize, isize)> = HashSet::new();
-        let mut rocks: HashSet<(isize, isize)> = HashSet::new();
-        let mut position: (isize, isize) = (0, 0);
-        for (i, line) in lines.get(0..height).unwrap().iter().enumerate() {
+    fn from(input: &str) -> Result<Self, ParseError> {
+        let sections = sections(input);
+        let [map_lines, moves] = &sections[..] else {
+            return Err(ParseError::missing(
+                &lines(input),
+                "a map and moves separated by a blank line",
+            ));
+        };
+        let map = grid(map_lines, "#.O@")?;
+        let width = map[0].len() as isize;
+        let height = map.len();
+        let mut boxes: HashSet<IJ> = HashSet::new();
+        let mut rocks: HashSet<IJ> = HashSet::new();
+        for (i, line) in map.iter().enumerate() {
             for (j, c) in line.chars().enumerate() {
-                let p = (i as isize, j as isize);
+                let p = IJ::from((i as isize, j as isize));
                 match c {
                     '#' => _ = rocks.insert(p),
                     'O' => _ = boxes.insert(p),
-                    '@' => position = p,
                     _ => (),
                 }
             }
         }
-        let mut instructions: Vec<char> = vec![];
-        for line in lines.get(height + 1..).unwrap() {
-            instructions.extend(line.chars());
+        let (i, j) = only_one(map_lines, '@', "a robot `@`")?;
+        let position = IJ::from((i as isize, j as isize));
+        let mut instructions: Vec<Direction> = vec![];
+        for line in moves {
+            line.only("<>^v")?;
+            instructions.extend(line.text.chars().filter_map(Direction::from_char));
         }
-        Self {
+        Ok(Self {
             height: height as isize,
             width,
             boxes,
             rocks,
             instructions,
             position,
-        }
+        })
     }
 
-    fn wide_from(lines: &[&str]) -> Self {
-        let thin = Self::from(lines);
-        let boxes: HashSet<(isize, isize)> = thin.boxes.iter().map(|&(i, j)| (i, 2 * j)).collect();
-        let rocks: HashSet<(isize, isize)> = thin
+    /// The same warehouse with everything except the robot twice as wide.
+    fn wide(&self) -> Self {
+        let double = |p: &IJ| IJ { i: p.i, j: 2 * p.j };
+        let boxes: HashSet<IJ> = self.boxes.iter().map(double).collect();
+        let rocks: HashSet<IJ> = self
             .rocks
             .iter()
-            .flat_map(|&(i, j)| vec![(i, 2 * j), (i, 2 * j + 1)])
+            .map(double)
+            .flat_map(|p| vec![p, p + IJ { i: 0, j: 1 }])
             .collect();
-        let position = (thin.position.0, 2 * thin.position.1);
+        let position = double(&self.position);
         Self {
-            height: thin.height,
-            width: 2 * thin.width,
+            height: self.height,
+            width: 2 * self.width,
             boxes,
             rocks,
-            instructions: thin.instructions,
+            instructions: self.instructions.clone(),
             position,
         }
     }
 
-    fn direction(inst: char) -> (isize, isize) {
-        match inst {
-            '>' => (0, 1),
-            '<' => (0, -1),
-            'v' => (1, 0),
-            '^' => (-1, 0),
-            _ => panic!("wrong instruction"),
-        }
-    }
-
     fn do_move(&mut self, inst_idx: usize) {
-        let inst = self.instructions[inst_idx];
-        let dir = Self::direction(inst);
+        let dir = self.instructions[inst_idx].offset();
         let next_nobox_dist = (1..self.height.max(self.width))
-            .find(|&t| {
-                !self
-                    .boxes
-                    .contains(&(self.position.0 + t * dir.0, self.position.1 + t * dir.1))
-            })
+            .find(|&t| !self.boxes.contains(&(self.position + t * dir)))
             .unwrap();
-        let nobox_pos = (
-            self.position.0 + next_nobox_dist * dir.0,
-            self.position.1 + next_nobox_dist * dir.1,
-        );
+        let nobox_pos = self.position + next_nobox_dist * dir;
         if !self.rocks.contains(&nobox_pos) {
-            let next_position = (self.position.0 + dir.0, self.position.1 + dir.1);
+            let next_position = self.position + dir;
             if next_nobox_dist > 1 {
                 self.boxes.remove(&next_position);
                 self.boxes.insert(nobox_pos);
@@ -98,7 +97,7 @@ impl Maze {
         }
     }
 
-    fn wide_occupies(&self, pos: (isize, isize)) -> (u8, (isize, isize)) {
+    fn wide_occupies(&self, pos: IJ) -> (u8, IJ) {
         // 0: unoccupied, pos
         // 1: box, pos of box (might be to the left of pos)
         // 2: rock, pos of rock
@@ -108,15 +107,16 @@ impl Maze {
         if self.boxes.contains(&pos) {
             return (1, pos);
         }
-        if self.boxes.contains(&(pos.0, pos.1 - 1)) {
-            return (1, (pos.0, pos.1 - 1));
+        let left = pos + IJ { i: 0, j: -1 };
+        if self.boxes.contains(&left) {
+            return (1, left);
         }
         (0, pos)
     }
 
-    fn wide_do_move(&mut self, inst: char) {
-        let dir = Self::direction(inst);
-        let next_position = (self.position.0 + dir.0, self.position.1 + dir.1);
+    fn wide_do_move(&mut self, inst: Direction) {
+        let dir = inst.offset();
+        let next_position = self.position + dir;
         let (o, p) = self.wide_occupies(next_position);
         if o == 0 {
             self.position = next_position;
@@ -124,7 +124,7 @@ impl Maze {
             if let Some(tomove) = self.moving_boxes(p, dir) {
                 self.boxes = self.boxes.difference(&tomove).copied().collect();
                 for p in tomove {
-                    self.boxes.insert((p.0 + dir.0, p.1 + dir.1));
+                    self.boxes.insert(p + dir);
                 }
                 self.position = next_position;
             }
@@ -132,94 +132,123 @@ impl Maze {
     }
 
     fn coordinate_sum(&self) -> isize {
-        self.boxes.iter().map(|&(i, j)| 100 * i + j).sum()
+        self.boxes.iter().map(|p| 100 * p.i + p.j).sum()
     }
 
-    fn moving_boxes(
-        &self,
-        p: (isize, isize),
-        dir: (isize, isize),
-    ) -> Option<HashSet<(isize, isize)>> {
-        let mut ret: HashSet<(isize, isize)> = HashSet::new();
-        let mut nextline: HashSet<(isize, isize)> = HashSet::from([p]);
+    fn moving_boxes(&self, p: IJ, dir: IJ) -> Option<HashSet<IJ>> {
+        let mut ret: HashSet<IJ> = HashSet::new();
+        let mut nextline: HashSet<IJ> = HashSet::from([p]);
         while !nextline.is_empty() {
-            let nextoccupied: HashSet<(isize, isize)> = if dir.0 == 0 {
+            let nextoccupied: HashSet<IJ> = if dir.i == 0 {
                 nextline
                     .iter()
-                    .map(|&(i, j)| (i, j + dir.1 + (dir.1 + 1) / 2))
+                    .map(|&p| {
+                        p + IJ {
+                            i: 0,
+                            j: dir.j + (dir.j + 1) / 2,
+                        }
+                    })
                     .collect()
             } else {
                 nextline
                     .iter()
-                    .flat_map(|&(i, j)| vec![(i + dir.0, j), (i + dir.0, j + 1)])
+                    .flat_map(|&p| vec![p + dir, p + dir + IJ { i: 0, j: 1 }])
                     .collect()
             };
             if !self
                 .rocks
                 .intersection(&nextoccupied)
                 .copied()
-                .collect::<Vec<(isize, isize)>>()
+                .collect::<Vec<IJ>>()
                 .is_empty()
             {
                 return None;
             }
-            let nextboxes: HashSet<(isize, isize)> = if dir.0 == 0 {
-                nextline.iter().map(|&(i, j)| (i, j + 2 * dir.1)).collect()
+            let nextboxes: HashSet<IJ> = if dir.i == 0 {
+                nextline.iter().map(|&p| p + 2 * dir).collect()
             } else {
                 nextline
                     .iter()
-                    .flat_map(|&(i, j)| {
-                        vec![(i + dir.0, j - 1), (i + dir.0, j), (i + dir.0, j + 1)]
-                    })
+                    .flat_map(|&p| (-1..=1).map(move |j| p + dir + IJ { i: 0, j }))
                     .collect()
             };
-            ret.extend(nextline.into_iter());
+            ret.extend(nextline);
             nextline = nextboxes.intersection(&self.boxes).copied().collect();
         }
         Some(ret)
     }
 
-    #[allow(dead_code)]
-    fn display(&self) {
+    /// Boxes take two places when `wide`.
+    fn display(&self, wide: bool) -> String {
         let mut d: Vec<String> =
             vec![String::from_iter(vec![' '; self.width as usize]); self.height as usize];
         for p in self.rocks.iter() {
-            d[p.0 as usize].replace_range((p.1 as usize)..(p.1 as usize + 1), "#");
+            d[p.i as usize].replace_range((p.j as usize)..(p.j as usize + 1), "#");
         }
+        let (box_str, box_width) = if wide { ("[]", 2) } else { ("O", 1) };
         for p in self.boxes.iter() {
-            d[p.0 as usize].replace_range((p.1 as usize)..(p.1 as usize + 2), "[]");
+            d[p.i as usize].replace_range((p.j as usize)..(p.j as usize + box_width), box_str);
         }
-        d[self.position.0 as usize].replace_range(
-            (self.position.1 as usize)..(self.position.1 as usize + 1),
+        d[self.position.i as usize].replace_range(
+            (self.position.j as usize)..(self.position.j as usize + 1),
             "@",
         );
-        println!("{}", String::from_iter(vec!['='; self.width as usize]));
-        d.iter().for_each(|l| println!("{l}"));
-        println!("{}", String::from_iter(vec!['='; self.width as usize]));
+        let border = String::from_iter(vec!['='; self.width as usize]);
+        format!("{border}\n{}\n{border}\n", d.join("\n"))
     }
 }
 
-fn prob1(input: &[&str]) -> isize {
-    let mut m = Maze::from(input);
+fn prob1(maze: &Maze) -> isize {
+    let mut m = maze.clone();
+    visual::frame(|| m.display(false));
     for i in 0..m.instructions.len() {
         m.do_move(i);
+        visual::frame(|| m.display(false));
     }
     m.coordinate_sum()
 }
 
-fn prob2(input: &[&str]) -> isize {
-    let mut m = Maze::wide_from(input);
+fn prob2(maze: &Maze) -> isize {
+    let mut m = maze.wide();
     let insts = m.instructions.clone();
+    visual::frame(|| m.display(true));
     for c in insts {
         m.wide_do_move(c);
+        visual::frame(|| m.display(true));
     }
     m.coordinate_sum()
 }
 
-pub(crate) fn main() {
-    let input: Vec<&str> = include_str!("../input/day_15").trim().split("\n").collect();
-    println!("prob1: {}", prob1(&input.clone()));
-    println!("prob2: {}", prob2(&input.clone()));
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Maze;
+    const DAY: usize = 15;
+    const TITLE: &'static str = "Warehouse Woes";
+    const CONSTRAINTS: &'static [Constraint] = &[
+        Constraint::Blocks(2),
+        Constraint::Block(
+            0,
+            &[
+                Constraint::Rectangular,
+                Constraint::Chars("#.O@"),
+                Constraint::One('@'),
+            ],
+        ),
+        Constraint::Block(1, &[Constraint::Chars("<>^v")]),
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Maze::from(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        prob2(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -227,6 +256,19 @@ pub mod tests {
     use std::collections::HashSet;
 
     use super::{prob1, prob2, Maze};
+    use crate::utils::{Direction::*, IJ};
+
+    fn ij(i: isize, j: isize) -> IJ {
+        IJ { i, j }
+    }
+
+    fn ijs<const N: usize>(ps: [(isize, isize); N]) -> HashSet<IJ> {
+        HashSet::from(ps.map(IJ::from))
+    }
+
+    fn maze(lines: &[&str]) -> Maze {
+        Maze::from(&lines.join("\n")).unwrap()
+    }
 
     fn input() -> Vec<&'static str> {
         vec![
@@ -256,66 +298,69 @@ pub mod tests {
 
     #[test]
     fn test_prob1() {
-        assert_eq!(prob1(&input()), 10092);
+        assert_eq!(prob1(&maze(&input())), 10092);
     }
 
     #[test]
     fn test_maze() {
-        let m = Maze::from(&input());
+        let m = maze(&input());
         assert_eq!(m.height, 10);
         assert_eq!(m.width, 10);
         assert_eq!(m.rocks.len(), 10 * 4 - 4 + 1);
         assert_eq!(m.boxes.len(), 21);
-        assert_eq!(m.position, (4, 4));
+        assert_eq!(m.position, ij(4, 4));
         assert_eq!(m.instructions.len(), 70 * 10);
-        assert_eq!(
-            m.instructions.get(..4).unwrap(),
-            "<vv>".chars().collect::<Vec<char>>()
-        )
+        assert_eq!(m.instructions.get(..4).unwrap(), [West, South, South, East]);
+
+        let e = Maze::from("#####\n#.@O#\n#####").err().unwrap();
+        assert_eq!(e.line, 4);
+        let e = Maze::from("#####\n#...#\n#####\n\n>").err().unwrap();
+        assert_eq!(e.expected, "a robot `@`");
+        let e = Maze::from("#####\n#.@O#\n#####\n\n>\n<x").err().unwrap();
+        assert_eq!((e.line, e.column, e.text.as_str()), (6, 2, "x"));
     }
 
     #[test]
     fn test_move() {
-        let mut m = Maze::from(&input());
+        let mut m = maze(&input());
         m.do_move(0);
-        assert!(m.boxes.contains(&(4, 2)));
-        assert_eq!(m.position, (4, 3));
+        assert!(m.boxes.contains(&ij(4, 2)));
+        assert_eq!(m.position, ij(4, 3));
         assert_eq!(m.boxes.len(), 21);
         for i in 1..m.instructions.len() {
             m.do_move(i);
         }
         assert_eq!(m.boxes.len(), 21);
-        assert_eq!(m.position, (4, 3));
+        assert_eq!(m.position, ij(4, 3));
     }
 
     #[test]
     fn test_wide_maze() {
-        let mut m = Maze::wide_from(&input());
+        let mut m = maze(&input()).wide();
         assert_eq!(m.width, 20);
         assert_eq!(m.height, 10);
         assert_eq!(m.boxes.len(), 21);
         assert_eq!(m.rocks.len(), 74);
-        assert!(m.boxes.contains(&(4, 6)));
-        assert_eq!(m.wide_occupies((4, 7)), (1, (4, 6)));
-        m.wide_do_move('<');
-        assert!(m.boxes.contains(&(4, 5)));
-        assert_eq!(m.position, (4, 7));
-        m.wide_do_move('^');
+        assert!(m.boxes.contains(&ij(4, 6)));
+        assert_eq!(m.wide_occupies(ij(4, 7)), (1, ij(4, 6)));
+        m.wide_do_move(West);
+        assert!(m.boxes.contains(&ij(4, 5)));
+        assert_eq!(m.position, ij(4, 7));
+        m.wide_do_move(North);
         assert_eq!(m.boxes.len(), 21);
-        assert_eq!(m.position, (3, 7));
-        assert!(m.boxes.contains(&(2, 6)));
-        m.wide_do_move('^');
-        assert_eq!(m.position, (3, 7));
-
-        let mut m = Maze::wide_from(&input());
-        m.wide_do_move('<');
-        m.wide_do_move('v');
-        m.wide_do_move('<');
-        m.wide_do_move('^');
-        let some: HashSet<(isize, isize)> = HashSet::from([(2, 4), (2, 6), (3, 5)]);
-        assert!(m.boxes.is_superset(&some));
+        assert_eq!(m.position, ij(3, 7));
+        assert!(m.boxes.contains(&ij(2, 6)));
+        m.wide_do_move(North);
+        assert_eq!(m.position, ij(3, 7));
+
+        let mut m = maze(&input()).wide();
+        m.wide_do_move(West);
+        m.wide_do_move(South);
+        m.wide_do_move(West);
+        m.wide_do_move(North);
+        assert!(m.boxes.is_superset(&ijs([(2, 4), (2, 6), (3, 5)])));
         let prev_boxes = m.boxes.clone();
-        m.wide_do_move('^');
+        m.wide_do_move(North);
         assert_eq!(m.boxes, prev_boxes);
     }
 
@@ -325,23 +370,23 @@ pub mod tests {
 
     #[test]
     fn test_wide_move() {
-        let mut m = Maze::wide_from(&input());
-        m.wide_do_move('^');
-        assert!(m.boxes.is_superset(&HashSet::from([(3, 4), (3, 6)])));
-        m.wide_do_move('<');
-        assert!(m.boxes.is_superset(&HashSet::from([(3, 3), (3, 5)])));
-        m.wide_do_move('<');
-        assert!(m.boxes.is_superset(&HashSet::from([(3, 2), (3, 4)])));
-        let mut m = Maze::wide_from(&input2());
-        assert_eq!(m.boxes, HashSet::from([(1, 6)]));
-        m.wide_do_move('>');
-        assert_eq!(m.boxes, HashSet::from([(1, 6)]));
-        m.wide_do_move('>');
-        assert_eq!(m.boxes, HashSet::from([(1, 6)]));
+        let mut m = maze(&input()).wide();
+        m.wide_do_move(North);
+        assert!(m.boxes.is_superset(&ijs([(3, 4), (3, 6)])));
+        m.wide_do_move(West);
+        assert!(m.boxes.is_superset(&ijs([(3, 3), (3, 5)])));
+        m.wide_do_move(West);
+        assert!(m.boxes.is_superset(&ijs([(3, 2), (3, 4)])));
+        let mut m = maze(&input2()).wide();
+        assert_eq!(m.boxes, ijs([(1, 6)]));
+        m.wide_do_move(East);
+        assert_eq!(m.boxes, ijs([(1, 6)]));
+        m.wide_do_move(East);
+        assert_eq!(m.boxes, ijs([(1, 6)]));
     }
 
     #[test]
     fn test_prob2() {
-        assert_eq!(prob2(&input()), 9021);
+        assert_eq!(prob2(&maze(&input())), 9021);
     }
 }
@@ -4,54 +4,55 @@ use std::{
     iter,
 };
 
-use crate::utils::DiGraph;
-
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
-enum Orientation {
-    East,
-    South,
-    West,
-    North,
-}
-impl Orientation {
-    fn iter() -> impl Iterator<Item = Orientation> {
-        [
-            Orientation::East,
-            Orientation::South,
-            Orientation::West,
-            Orientation::North,
-        ]
-        .iter()
-        .copied()
-    }
-    fn clock() -> Vec<(Orientation, Orientation)> {
-        let oris: Vec<Orientation> = Self::iter().collect();
-        (0..oris.len())
-            .map(|i| (oris[i], oris[(i + 1) % 4]))
-            .collect()
-    }
-    fn direction() -> Vec<(Orientation, (isize, isize))> {
-        vec![
-            (Orientation::East, (0, 1)),
-            (Orientation::South, (1, 0)),
-            (Orientation::West, (0, -1)),
-            (Orientation::North, (-1, 0)),
-        ]
-    }
-}
+use crate::{
+    answer::Answer,
+    lint::Constraint,
+    parse::{grid, lines, only_one, ParseError},
+    solution::{Params, Solution},
+    utils::{DiGraph, Direction, IJ},
+    visual,
+};
 
-type Ijo = (isize, isize, Orientation);
+type Ijo = (isize, isize, Direction);
 
-struct Maze {
+pub struct Maze {
     graph: DiGraph<Ijo>,
     weights: HashMap<(Ijo, Ijo), isize>,
-    start: (isize, isize, Orientation),
+    start: (isize, isize, Direction),
     end: (isize, isize),
+    /// rows and columns
+    size: (usize, usize),
+}
+
+impl Maze {
+    /// The maze with `tiles` marked with `O`.
+    fn render(&self, tiles: &HashSet<(isize, isize)>) -> String {
+        let cell = |(i, j): (isize, isize)| (i as usize, j as usize);
+        let open: Vec<(usize, usize)> = self
+            .graph
+            .nodes()
+            .iter()
+            .map(|&(i, j, _)| cell((i, j)))
+            .collect();
+        let tiles: Vec<(usize, usize)> = tiles.iter().map(|&ij| cell(ij)).collect();
+        let start = [cell((self.start.0, self.start.1))];
+        let end = [cell(self.end)];
+        visual::draw(
+            self.size.0,
+            self.size.1,
+            '#',
+            &[('.', &open), ('O', &tiles), ('S', &start), ('E', &end)],
+        )
+    }
 }
 
-fn parse(input: &[&str]) -> Maze {
-    let mut start: Ijo = (0, 0, Orientation::East);
-    let mut end: (isize, isize) = (0, 0);
+fn parse(input: &str) -> Result<Maze, ParseError> {
+    let lines = lines(input);
+    let input = grid(&lines, "#.SE")?;
+    let (si, sj) = only_one(&lines, 'S', "a start `S`")?;
+    let (ei, ej) = only_one(&lines, 'E', "an end `E`")?;
+    let start: Ijo = (si as isize, sj as isize, Direction::East);
+    let end: (isize, isize) = (ei as isize, ej as isize);
     let mut nodes: Vec<Ijo> = vec![];
     let edges: Vec<(Ijo, Ijo)> = vec![];
     let maxi = input.len() as isize;
@@ -61,19 +62,15 @@ fn parse(input: &[&str]) -> Maze {
         let ii = i as isize;
         for (j, c) in line.chars().enumerate() {
             let ij = j as isize;
-            if c == 'S' {
-                start = (i as isize, j as isize, Orientation::East)
-            } else if c == 'E' {
-                end = (i as isize, j as isize);
-            }
             if c != '#' {
-                nodes.extend(Orientation::iter().map(|io| (ii, ij, io)));
-                for (oa, ob) in Orientation::clock() {
+                nodes.extend(Direction::ALL.map(|io| (ii, ij, io)));
+                for oa in Direction::ALL {
+                    let ob = oa.turn_right();
                     weights.insert(((ii, ij, oa), (ii, ij, ob)), 1000);
                     weights.insert(((ii, ij, ob), (ii, ij, oa)), 1000);
                 }
-                for (oa, dir) in Orientation::direction() {
-                    let (ii1, ij1) = (ii + dir.0, ij + dir.1);
+                for oa in Direction::ALL {
+                    let IJ { i: ii1, j: ij1 } = IJ { i: ii, j: ij } + oa;
                     if (0 <= ii1 && ii1 < maxi && 0 <= ij1 && ij1 < maxj)
                         && &input[ii1 as usize][ij1 as usize..ij1 as usize + 1] != "#"
                     {
@@ -84,33 +81,30 @@ fn parse(input: &[&str]) -> Maze {
         }
     }
     let graph: DiGraph<Ijo> = DiGraph::from(nodes, edges);
-    Maze {
+    Ok(Maze {
         graph,
         weights,
         start,
         end,
-    }
+        size: (maxi as usize, maxj as usize),
+    })
 }
 
-fn prob1(input: &[&str]) -> isize {
-    let m = parse(input);
+fn prob1(m: &Maze) -> isize {
     *m.graph
         .distance_with(
             m.start,
-            Orientation::iter()
-                .map(|o| (m.end.0, m.end.1, o))
-                .collect::<Vec<Ijo>>(),
-            m.weights,
+            Direction::ALL.map(|o| (m.end.0, m.end.1, o)),
+            m.weights.clone(),
         )
         .values()
         .min()
         .unwrap()
 }
 
-fn prob2(input: &[&str]) -> usize {
-    let mindist = Reverse(prob1(input));
-    let m = parse(input);
-    let ends: Vec<Ijo> = Orientation::iter().map(|o| (m.end.0, m.end.1, o)).collect();
+fn prob2(m: &Maze) -> usize {
+    let mindist = Reverse(prob1(m));
+    let ends: Vec<Ijo> = Direction::ALL.map(|o| (m.end.0, m.end.1, o)).to_vec();
     // somewhat copy distance_with but keeping track of paths
     let mut wchildren: HashMap<Ijo, HashSet<Ijo>> = HashMap::new();
     for (f, t) in m.weights.keys().copied() {
@@ -170,18 +164,40 @@ fn prob2(input: &[&str]) -> usize {
         }
     }
     let ret: HashSet<(isize, isize)> = ret.iter().map(|&(i, j, _)| (i, j)).collect();
+    visual::frame(|| m.render(&ret));
     ret.len()
 }
 
-pub(crate) fn main() {
-    let input: Vec<&str> = include_str!("../input/day_16").trim().split("\n").collect();
-    println!("prob1: {}", prob1(&input.clone()));
-    println!("prob2: {}", prob2(&input.clone()));
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Maze;
+    const DAY: usize = 16;
+    const TITLE: &'static str = "Reindeer Maze";
+    const CONSTRAINTS: &'static [Constraint] = &[
+        Constraint::Rectangular,
+        Constraint::Chars("#.SE"),
+        Constraint::One('S'),
+        Constraint::One('E'),
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        prob2(input).into()
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
-    use super::{parse, prob1, prob2, Orientation};
+    use super::{parse, prob1, prob2};
+    use crate::utils::Direction;
 
     fn input() -> Vec<&'static str> {
         vec![
@@ -205,19 +221,21 @@ pub mod tests {
 
     #[test]
     fn test_parse() {
-        let maze = parse(&input());
+        let maze = parse(&input().join("\n")).unwrap();
         assert_eq!(maze.graph.len(), 416);
-        assert_eq!(maze.start, (13, 1, Orientation::East));
+        assert_eq!(maze.start, (13, 1, Direction::East));
         assert_eq!(maze.end, (1, 13));
+        let e = parse("####\n#SE#\n#E.#\n####").err().unwrap();
+        assert_eq!((e.line, e.column), (3, 2));
     }
 
     #[test]
     fn test_prob1() {
-        assert_eq!(prob1(&input()), 7036);
+        assert_eq!(prob1(&parse(&input().join("\n")).unwrap()), 7036);
     }
 
     #[test]
     fn test_prob2() {
-        assert_eq!(prob2(&input()), 45);
+        assert_eq!(prob2(&parse(&input().join("\n")).unwrap()), 45);
     }
 }
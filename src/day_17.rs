@@ -1,7 +1,14 @@
 use regex::Regex;
 
+use crate::{
+    answer::Answer,
+    cancel,
+    parse::{lines, sections, Line, ParseError},
+    solution::{Params, Solution},
+};
+
 #[derive(Clone)]
-struct Machine {
+pub struct Machine {
     rega: isize,
     regb: isize,
     regc: isize,
@@ -11,50 +18,82 @@ struct Machine {
 }
 
 impl Machine {
-    fn _getreg(line: &str) -> isize {
+    fn _getreg(line: &Line, name: char) -> Result<isize, ParseError> {
         let regreg = Regex::new("^Register ([ABC]): (\\d+)$").unwrap();
-        regreg
-            .captures(line)
-            .unwrap()
-            .get(2)
-            .unwrap()
-            .as_str()
-            .parse()
-            .unwrap()
-    }
-
-    fn parse(input: &[&str]) -> Self {
-        let rega = Self::_getreg(input[0]);
-        let regb = Self::_getreg(input[1]);
-        let regc = Self::_getreg(input[2]);
-        let inst_args: Vec<isize> = input[4]
-            .split(":")
-            .last()
-            .unwrap()
-            .trim()
+        let cap = line.captures(&regreg, &format!("`Register {name}: <number>`"))?;
+        let reg = cap.get(1).unwrap().as_str();
+        if !reg.starts_with(name) {
+            return Err(line.error_at(reg, format!("register {name}")));
+        }
+        line.number(cap.get(2).unwrap().as_str())
+    }
+
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let sections = sections(input);
+        let [regs, prog] = &sections[..] else {
+            return Err(ParseError::missing(
+                &lines(input),
+                "registers and a program separated by a blank line",
+            ));
+        };
+        let [a, b, c] = &regs[..] else {
+            return Err(match regs.get(3) {
+                Some(extra) => extra.error("a blank line before the program"),
+                None => ParseError::missing(regs, "three registers"),
+            });
+        };
+        let rega = Self::_getreg(a, 'A')?;
+        let regb = Self::_getreg(b, 'B')?;
+        let regc = Self::_getreg(c, 'C')?;
+        let [prog] = &prog[..] else {
+            return Err(prog[1].error("nothing after the program"));
+        };
+        let Some(values) = prog.text.strip_prefix("Program: ") else {
+            return Err(prog.error("`Program: ` and a list of numbers"));
+        };
+        let inst_args: Vec<isize> = values
             .split(",")
-            .map(|i| i.parse().unwrap())
-            .collect();
+            .map(|i| match prog.number(i)? {
+                n @ 0..=7 => Ok(n),
+                _ => Err(prog.error_at(i, "a 3-bit number")),
+            })
+            .collect::<Result<_, _>>()?;
+        if inst_args.len() % 2 == 1 {
+            return Err(prog.error_at("", "an operand after the last instruction"));
+        }
         let program: Vec<(isize, isize)> = inst_args
             .iter()
             .step_by(2)
             .copied()
             .zip(inst_args.iter().skip(1).step_by(2).copied())
             .collect();
-        Self {
+        Ok(Self {
             rega,
             regb,
             regc,
             program,
             execptr: 0,
             output: vec![],
-        }
+        })
     }
 
     fn stopped(&self) -> bool {
         self.execptr >= self.program.len()
     }
 
+    /// Executes until it stops, or until told to give up, since a program
+    /// may loop forever.
+    fn run(&mut self) {
+        let mut steps = 0usize;
+        while !self.stopped() {
+            if steps.is_multiple_of(4096) && cancel::requested() {
+                return;
+            }
+            self.exec();
+            steps += 1;
+        }
+    }
+
     // a % 8 ^ 3 ^ 5 ^ c
     // c -> a / 2**(a % 8 ^ 3)
 
@@ -95,11 +134,9 @@ impl Machine {
     }
 }
 
-fn prob1(input: &[&str]) -> String {
-    let mut m = Machine::parse(input);
-    while !m.stopped() {
-        m.exec();
-    }
+fn prob1(m0: &Machine) -> String {
+    let mut m = m0.clone();
+    m.run();
     m.output
         .iter()
         .map(|n| n.to_string())
@@ -124,10 +161,9 @@ fn check(rega: Vec<isize>, m0: &Machine, expected: &[isize]) -> bool {
         .copied()
         .collect();
     m.rega = b8_isize(&nrega);
-    while !m.stopped() {
-        m.exec();
-    }
-    m.output[len - rega.len()] == expected[len - rega.len()]
+    m.run();
+    // short if it gave up
+    m.output.get(len - rega.len()) == Some(&expected[len - rega.len()])
 }
 
 /// Looking at the program, each cycle it prints one value
@@ -154,23 +190,39 @@ fn tree_check(rega: Vec<isize>, m0: &Machine, expected: &[isize]) -> Option<Vec<
     None
 }
 
-fn prob2(input: &[&str]) -> isize {
-    let m0 = Machine::parse(input);
+fn prob2(m0: &Machine) -> isize {
     let expected: Vec<isize> = m0
         .program
         .iter()
         .flat_map(|&(i, a)| [i, a].into_iter())
         .collect();
-    if let Some(ret) = tree_check(vec![], &m0, &expected) {
+    if let Some(ret) = tree_check(vec![], m0, &expected) {
         return b8_isize(&ret);
     }
+    if cancel::requested() {
+        return 0;
+    }
     panic!("No value found");
 }
 
-pub(crate) fn main() {
-    let input: Vec<&str> = include_str!("../input/day_17").trim().split("\n").collect();
-    println!("prob1: {}", prob1(&input.clone()));
-    println!("prob2: {}", prob2(&input.clone()));
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Machine;
+    const DAY: usize = 17;
+    const TITLE: &'static str = "Chronospatial Computer";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Machine::parse(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        prob2(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -197,15 +249,29 @@ pub mod tests {
         ]
     }
 
+    fn machine(lines: &[&str]) -> Machine {
+        Machine::parse(&lines.join("\n")).unwrap()
+    }
+
     #[test]
     fn test_machine() {
-        let m = Machine::parse(&input());
+        let m = machine(&input());
         assert_eq!(m.rega, 729);
         assert_eq!(m.regb, 0);
         assert_eq!(m.regc, 0);
         assert_eq!(m.program, vec![(0, 1), (5, 4), (3, 0)]);
         assert_eq!(m.execptr, 0);
         assert!(!m.stopped());
+
+        let parse_err = |text: &str| Machine::parse(text).err().unwrap();
+        let e = parse_err("Register A: 7\nRegister C: 0\nRegister C: 0\n\nProgram: 0,1");
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 10, "C"));
+        let e = parse_err("Register A: 7\nRegister B: 0\nRegister C: 0\n\nProgram: 0,8");
+        assert_eq!((e.line, e.column, e.text.as_str()), (5, 12, "8"));
+        let e = parse_err("Register A: 7\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5");
+        assert_eq!((e.line, e.column), (5, 15));
+        let e = parse_err("Register A: 7\nRegister B: 0\n\nProgram: 0,1");
+        assert_eq!((e.line, e.expected.as_str()), (3, "three registers"));
     }
 
     #[test]
@@ -235,11 +301,11 @@ pub mod tests {
 
     #[test]
     fn test_prob1() {
-        assert_eq!(prob1(&input()), "4,6,3,5,6,3,5,2,1,0");
+        assert_eq!(prob1(&machine(&input())), "4,6,3,5,6,3,5,2,1,0");
     }
 
     #[test]
     fn test_prob2() {
-        assert_eq!(prob2(&input2()), 117440);
+        assert_eq!(prob2(&machine(&input2())), 117440);
     }
 }
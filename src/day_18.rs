@@ -1,128 +1,219 @@
 use std::collections::HashSet;
 
-use crate::utils::DiGraph;
+use crate::{
+    answer::Answer,
+    parse::{lines, Line, ParseError},
+    solution::{Param, Params, Solution},
+    utils::{DiGraph, IJ},
+    visual,
+};
 
-fn shortest_path(xys: &[XY], cut_at: usize, width: isize, height: isize) -> Option<usize> {
+/// The memory space with the `fallen` bytes.
+fn render(fallen: &[IJ], width: isize, height: isize) -> String {
+    let fallen: Vec<(usize, usize)> = fallen
+        .iter()
+        .map(|ij| (ij.i as usize, ij.j as usize))
+        .collect();
+    visual::draw(height as usize, width as usize, '.', &[('#', &fallen)])
+}
+
+fn shortest_path(xys: &[IJ], cut_at: usize, width: isize, height: isize) -> Option<usize> {
+    visual::frame(|| render(&xys[0..cut_at], width, height));
     let graph = make_graph(&xys[0..cut_at], width, height);
-    let start = XY { x: 0, y: 0 };
-    let end = XY {
-        x: width - 1,
-        y: height - 1,
+    let start = IJ { i: 0, j: 0 };
+    let end = IJ {
+        i: height - 1,
+        j: width - 1,
     };
     graph.distance(start, end)
 }
 
-fn prob1(input: &[&str], cut_at: usize, width: isize, height: isize) -> usize {
-    let xys: Vec<XY> = parse(input);
-    shortest_path(&xys, cut_at, width, height).unwrap()
+/// `None` if the fallen bytes leave no way out.
+fn prob1(xys: &[IJ], cut_at: usize, width: isize, height: isize) -> Option<usize> {
+    shortest_path(xys, cut_at, width, height)
 }
 
-fn prob2(input: &[&str], width: isize, height: isize) -> XY {
-    let xys: Vec<XY> = parse(input);
+/// The first byte that leaves no way out, `None` if the way out is never
+/// blocked.
+fn prob2(xys: &[IJ], width: isize, height: isize) -> Option<IJ> {
+    if shortest_path(xys, xys.len(), width, height).is_some() {
+        return None;
+    }
+    // open with `min` bytes, blocked with `max`
     let mut min = 0;
     let mut max = xys.len();
     while max > min + 1 {
         let mid = (max + min) / 2;
-        if shortest_path(&xys, mid, width, height).is_some() {
+        if shortest_path(xys, mid, width, height).is_some() {
             min = mid;
         } else {
             max = mid;
         }
     }
-    xys[min]
+    Some(xys[min])
 }
 
-#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
-struct XY {
-    x: isize,
-    y: isize,
+/// `x,y` as the row `y` and the column `x`.
+fn byte(line: &Line) -> Result<IJ, ParseError> {
+    let (x, y) = line.split_once(",")?;
+    Ok(IJ {
+        i: line.number::<usize>(y)? as isize,
+        j: line.number::<usize>(x)? as isize,
+    })
 }
-impl XY {
-    fn from(line: &str) -> Self {
-        let xy: Vec<&str> = line.split(",").collect();
-        Self {
-            x: xy[0].parse().unwrap(),
-            y: xy[1].parse().unwrap(),
-        }
-    }
 
-    fn adjacent(&self, width: isize, height: isize) -> Vec<XY> {
-        let mut ret: Vec<XY> = vec![];
-        if self.x > 0 {
-            ret.push(XY {
-                x: self.x - 1,
-                y: self.y,
-            })
-        }
-        if self.x < width - 1 {
-            ret.push(XY {
-                x: self.x + 1,
-                y: self.y,
-            })
-        }
-        if self.y > 0 {
-            ret.push(XY {
-                x: self.x,
-                y: self.y - 1,
-            })
-        }
-        if self.y < height - 1 {
-            ret.push(XY {
-                x: self.x,
-                y: self.y + 1,
-            })
+fn make_graph(broken: &[IJ], width: isize, height: isize) -> DiGraph<IJ> {
+    let broken: HashSet<IJ> = broken.iter().copied().collect();
+    let (origin, size) = (
+        IJ { i: 0, j: 0 },
+        IJ {
+            i: height,
+            j: width,
+        },
+    );
+    let mut graph: DiGraph<IJ> = DiGraph::new();
+    let nodes = (0..width)
+        .flat_map(|j| (0..height).map(move |i| IJ { i, j }))
+        .filter(|ij| !broken.contains(ij));
+    for ij in nodes {
+        graph.add_node(ij);
+        for ij2 in ij.neighbors() {
+            if ij2.inside(origin, size) && !broken.contains(&ij2) {
+                graph.add_edge(ij, ij2);
+            }
         }
-        ret
     }
+    graph
 }
 
-fn make_graph(broken: &[XY], width: isize, height: isize) -> DiGraph<XY> {
-    let broken: HashSet<XY> = broken.iter().copied().collect();
-    let nodes: Vec<XY> = (0..width)
-        .flat_map(|x| (0..height).map(move |y| XY { x, y }))
-        .filter(|xy| !broken.contains(xy))
-        .collect();
-    let mut edges: Vec<(XY, XY)> = vec![];
-    for xy in nodes.iter().copied() {
-        for xy2 in xy.adjacent(width, height) {
-            if !broken.contains(&xy2) {
-                edges.push((xy, xy2));
-            }
+fn parse(input: &str) -> Result<Vec<IJ>, ParseError> {
+    lines(input).iter().map(byte).collect()
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<IJ>;
+    const DAY: usize = 18;
+    const TITLE: &'static str = "RAM Run";
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "cut_at",
+            default: 1024,
+            help: "bytes fallen in part 1",
+        },
+        Param {
+            name: "width",
+            default: 71,
+            help: "width of the memory space in part 1",
+        },
+        Param {
+            name: "height",
+            default: 71,
+            help: "height of the memory space in part 1",
+        },
+        Param {
+            name: "width2",
+            default: 72,
+            help: "width of the memory space in part 2",
+        },
+        Param {
+            name: "height2",
+            default: 72,
+            help: "height of the memory space in part 2",
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn check(input: &Self::Input, params: &Params) -> Result<(), String> {
+        params.positive(&["width", "height", "width2", "height2"])?;
+        let cut_at = params.get("cut_at");
+        if cut_at > input.len() {
+            return Err(format!(
+                "`cut_at` is {cut_at}, but only {} bytes fall",
+                input.len()
+            ));
         }
+        Ok(())
     }
-    DiGraph { nodes, edges }
-}
 
-fn parse(input: &[&str]) -> Vec<XY> {
-    input.iter().map(|l| XY::from(l)).collect()
-}
+    fn part1(input: &Self::Input, params: &Params) -> Answer {
+        let (width, height) = (params.get("width"), params.get("height"));
+        match prob1(input, params.get("cut_at"), width as isize, height as isize) {
+            Some(steps) => steps.into(),
+            None => "no way out".into(),
+        }
+    }
 
-pub(crate) fn main() {
-    let input: Vec<&str> = include_str!("../input/day_18").trim().split("\n").collect();
-    println!("prob1: {}", prob1(&input.clone(), 1024, 71, 71));
-    let p2 = prob2(&input.clone(), 72, 72);
-    println!("prob2: {},{}", p2.x, p2.y);
+    fn part2(input: &Self::Input, params: &Params) -> Answer {
+        let (width, height) = (params.get("width2"), params.get("height2"));
+        match prob2(input, width as isize, height as isize) {
+            Some(p2) => (p2.j, p2.i).into(),
+            None => "never blocked".into(),
+        }
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
-    use super::{prob1, prob2, XY};
+    use super::{parse, prob1, prob2, Day18};
+    use crate::{
+        solution::{Params, Solution},
+        utils::IJ,
+    };
 
-    fn input() -> Vec<&'static str> {
-        vec![
+    fn input() -> Vec<IJ> {
+        let lines = [
             "5,4", "4,2", "4,5", "3,0", "2,1", "6,3", "2,4", "1,5", "0,6", "3,3", "2,6", "5,1",
             "1,2", "5,5", "2,5", "6,5", "1,4", "0,4", "6,4", "1,1", "6,1", "1,0", "0,5", "1,6",
             "2,0",
-        ]
+        ];
+        parse(&lines.join("\n")).unwrap()
     }
 
     #[test]
     fn test_prob1() {
-        assert_eq!(prob1(&input(), 12, 7, 7), 22);
+        assert_eq!(prob1(&input(), 12, 7, 7), Some(22));
+        assert_eq!(prob1(&input(), 25, 7, 7), None);
+        // a byte on the exit
+        assert_eq!(prob1(&[IJ { i: 6, j: 6 }], 1, 7, 7), None);
     }
 
     #[test]
     fn test_prob2() {
-        assert_eq!(prob2(&input(), 7, 7), XY { x: 6, y: 1 });
+        assert_eq!(prob2(&input(), 7, 7), Some(IJ { i: 1, j: 6 }));
+        assert_eq!(prob2(&input(), 20, 20), None);
+        assert_eq!(prob2(&[], 7, 7), None);
+    }
+
+    #[test]
+    fn test_check() {
+        let params = |given: &[(&str, usize)]| {
+            let given: Vec<(String, usize)> =
+                given.iter().map(|&(n, v)| (n.to_string(), v)).collect();
+            Params::new(Day18::PARAMS, &given).unwrap()
+        };
+        let small = params(&[("cut_at", 12), ("width", 7), ("height", 7)]);
+        assert!(Day18::check(&input(), &small).is_ok());
+        assert_eq!(
+            Day18::check(&input(), &params(&[("cut_at", 26)])),
+            Err("`cut_at` is 26, but only 25 bytes fall".to_string())
+        );
+        assert_eq!(
+            Day18::check(&input(), &params(&[("cut_at", 12), ("height2", 0)])),
+            Err("`height2` must be at least 1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(input()[1], IJ { i: 2, j: 4 });
+        let e = parse("5,4\n4;2").unwrap_err();
+        assert_eq!((e.line, e.expected.as_str()), (2, "`,`"));
+        let e = parse("5,4\n4,-2").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 3, "-2"));
     }
 }
@@ -1,14 +1,49 @@
 use std::collections::HashMap;
 
+use crate::{
+    answer::Answer,
+    parse::{lines, sections, ParseError},
+    solution::{Params, Solution},
+};
+
+/// The towel patterns, and the designs to make with them.
+type Patterns = (Vec<String>, Vec<String>);
+
+fn parse(input: &str) -> Result<Patterns, ParseError> {
+    let sections = sections(input);
+    let [towels, designs] = &sections[..] else {
+        return Err(ParseError::missing(
+            &lines(input),
+            "towels and designs separated by a blank line",
+        ));
+    };
+    let [line] = &towels[..] else {
+        return Err(towels[1].error("a blank line after the towels"));
+    };
+    line.only("wubrg, ")?;
+    let mut patterns: Vec<String> = vec![];
+    for towel in line.text.split(", ") {
+        if towel.is_empty() {
+            return Err(line.error_at(towel, "a towel"));
+        }
+        patterns.push(towel.to_string());
+    }
+    for design in designs {
+        design.only("wubrg")?;
+    }
+    let designs = designs.iter().map(|l| l.text.to_string()).collect();
+    Ok((patterns, designs))
+}
+
 struct Towels<'a> {
     towels: Vec<&'a str>,
     onsets: Vec<&'a str>,
     ways: HashMap<String, usize>,
 }
 impl<'a> Towels<'a> {
-    fn parse(lines: &[&'a str]) -> Self {
-        let towels: Vec<&'a str> = lines[0].split(", ").collect();
-        let onsets: Vec<&'a str> = lines[2..].to_vec();
+    fn new((towels, onsets): &'a Patterns) -> Self {
+        let towels: Vec<&'a str> = towels.iter().map(|t| t.as_str()).collect();
+        let onsets: Vec<&'a str> = onsets.iter().map(|o| o.as_str()).collect();
         let ways: HashMap<String, usize> = HashMap::new();
         Self {
             towels,
@@ -58,8 +93,8 @@ impl<'a> Towels<'a> {
     }
 }
 
-fn prob1(input: &[&str]) -> usize {
-    let mut ts = Towels::parse(input);
+fn prob1(patterns: &Patterns) -> usize {
+    let mut ts = Towels::new(patterns);
     let mut ret = 0usize;
     for o in ts.onsets.clone().into_iter() {
         if ts.can_make(o.to_string()) {
@@ -69,21 +104,35 @@ fn prob1(input: &[&str]) -> usize {
     ret
 }
 
-fn prob2(input: &[&str]) -> usize {
-    let mut ts = Towels::parse(input);
+fn prob2(patterns: &Patterns) -> usize {
+    let mut ts = Towels::new(patterns);
     let ts_ts: Vec<String> = ts.onsets.iter().map(|t| t.to_string()).collect();
     ts_ts.iter().map(|t| ts.ways_to_make(t.to_string())).sum()
 }
 
-pub(crate) fn main() {
-    let input: Vec<&str> = include_str!("../input/day_19").trim().split("\n").collect();
-    println!("prob1: {}", prob1(&input.clone()));
-    println!("prob2: {}", prob2(&input.clone()));
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Patterns;
+    const DAY: usize = 19;
+    const TITLE: &'static str = "Linen Layout";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        prob2(input).into()
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
-    use super::{prob1, prob2};
+    use super::{parse, prob1, prob2};
 
     fn input() -> Vec<&'static str> {
         vec![
@@ -102,11 +151,21 @@ pub mod tests {
 
     #[test]
     fn test_prob1() {
-        assert_eq!(prob1(&input()), 6);
+        assert_eq!(prob1(&parse(&input().join("\n")).unwrap()), 6);
     }
 
     #[test]
     fn test_prob2() {
-        assert_eq!(prob2(&input()), 16);
+        assert_eq!(prob2(&parse(&input().join("\n")).unwrap()), 16);
+    }
+
+    #[test]
+    fn test_parse() {
+        let (towels, designs) = parse(&input().join("\n")).unwrap();
+        assert_eq!((towels.len(), designs.len()), (8, 8));
+        let e = parse("r, wr, , b\n\nbrwrr").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 8));
+        let e = parse("r, wr\n\nbrwrr\nbrxrr").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (4, 3, "x"));
     }
 }
@@ -1,8 +1,11 @@
-fn parse_lines(lines: Vec<&str>) -> Vec<Vec<isize>> {
-    lines
-        .iter()
-        .map(|ln| ln.split(' ').map(|i| i.parse().unwrap()).collect())
-        .collect()
+use crate::{
+    answer::Answer,
+    parse::{lines, ParseError},
+    solution::{Params, Solution},
+};
+
+fn parse_lines(input: &str) -> Result<Vec<Vec<isize>>, ParseError> {
+    lines(input).iter().map(|ln| ln.numbers(" ")).collect()
 }
 
 fn is_safe(line: Vec<isize>) -> bool {
@@ -34,23 +37,35 @@ fn is_safe_removing_one(line: Vec<isize>) -> bool {
     })
 }
 
-fn prob1(lines: Vec<&str>) -> usize {
-    let lines = parse_lines(lines);
+fn prob1(lines: &[Vec<isize>]) -> usize {
     lines.iter().filter(|&v| is_safe(v.clone())).count()
 }
 
-fn prob2(lines: Vec<&str>) -> usize {
-    let lines = parse_lines(lines);
+fn prob2(lines: &[Vec<isize>]) -> usize {
     lines
         .iter()
         .filter(|&v| is_safe_removing_one(v.clone()))
         .count()
 }
 
-pub fn main() {
-    let lines: Vec<&str> = include_str!("../input/day_2").trim().split("\n").collect();
-    println!("prob1: {}", prob1(lines.clone()));
-    println!("prob2: {}", prob2(lines.clone()));
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Vec<isize>>;
+    const DAY: usize = 2;
+    const TITLE: &'static str = "Red-Nosed Reports";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        prob2(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -68,19 +83,25 @@ pub mod tests {
         ]
     }
 
+    fn parsed() -> Vec<Vec<isize>> {
+        parse_lines(&input().join("\n")).unwrap()
+    }
+
     #[test]
     fn test_parse() {
-        let pl = parse_lines(input());
+        let pl = parsed();
         assert_eq!(pl.len(), 6);
+        let e = parse_lines("7 6 4\n1 2  3\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 5));
     }
 
     #[test]
     fn test_p1() {
-        assert_eq!(prob1(input()), 2);
+        assert_eq!(prob1(&parsed()), 2);
     }
 
     #[test]
     fn test_p2() {
-        assert_eq!(prob2(input()), 4);
+        assert_eq!(prob2(&parsed()), 4);
     }
 }
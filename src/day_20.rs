@@ -1,24 +1,47 @@
 use itertools::iproduct;
 use std::collections::{HashMap, HashSet};
 
-use crate::utils::{Maze, IJ};
+use crate::{
+    answer::Answer,
+    cancel,
+    lint::Constraint,
+    parse::{grid, lines, only_one, ParseError},
+    solution::{Param, Params, Solution},
+    utils::{Maze, IJ},
+    visual,
+};
 
-fn prob1(input: &[&str], min_diff: usize) -> usize {
-    let input: Vec<String> = input.iter().map(|s| s.to_string()).collect();
-    let m = Maze::from(&input);
+/// `input` with `c` on `cells`.
+fn render(input: &[String], cells: &[IJ], c: char) -> String {
+    let mut rows: Vec<Vec<char>> = input.iter().map(|r| r.chars().collect()).collect();
+    for ij in cells {
+        rows[ij.i as usize][ij.j as usize] = c;
+    }
+    rows.iter().map(|r| String::from_iter(r) + "\n").collect()
+}
+
+fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    let lines = lines(input);
+    let rows = grid(&lines, "#.SE")?;
+    only_one(&lines, 'S', "a start `S`")?;
+    only_one(&lines, 'E', "an end `E`")?;
+    Ok(rows)
+}
+
+fn prob1(input: &[String], min_diff: usize) -> usize {
+    let m = Maze::from(input);
     let d0 = m.distance().unwrap();
 
     let mut mdig = m.as_digraph();
     let wall = m.wall.clone();
     for &ij in wall.iter() {
-        mdig.nodes.push(ij);
+        mdig.add_node(ij);
         for kl in m.neighbors(ij) {
-            mdig.edges.push((kl, ij));
+            mdig.add_edge(kl, ij);
         }
     }
 
-    let weights: HashMap<(IJ, IJ), usize> =
-        mdig.edges.iter().map(|&(ij, kl)| ((ij, kl), 1)).collect();
+    let weights: HashMap<(IJ, IJ), usize> = mdig.edges().map(|(&ij, &kl)| ((ij, kl), 1)).collect();
     let start_dists = mdig.distance_with(m.start, wall.clone(), weights.clone());
     let end_dists = mdig.distance_with(m.end, wall.clone(), weights.clone());
 
@@ -31,12 +54,19 @@ fn prob1(input: &[&str], min_diff: usize) -> usize {
             )
         })
         .collect();
+    visual::frame(|| {
+        let cheats: Vec<IJ> = sum_dists
+            .iter()
+            .filter(|(_, &d)| d + min_diff <= d0)
+            .map(|(&ij, _)| ij)
+            .collect();
+        render(input, &cheats, '1')
+    });
     sum_dists.values().filter(|&d| d + min_diff <= d0).count()
 }
 
-fn prob2(input: &[&str], max_dist: isize, min_save: usize) -> usize {
-    let input: Vec<String> = input.iter().map(|s| s.to_string()).collect();
-    let m = Maze::from(&input);
+fn prob2(input: &[String], max_dist: isize, min_save: usize) -> usize {
+    let m = Maze::from(input);
     let mdig = m.as_digraph();
     let d0 = mdig.distance(m.start, m.end).unwrap();
     let input_rev: Vec<String> = input
@@ -49,8 +79,8 @@ fn prob2(input: &[&str], max_dist: isize, min_save: usize) -> usize {
         })
         .collect();
     let mdig_reverse = Maze::from(&input_rev).as_digraph();
-    let wall: HashSet<IJ> = mdig_reverse.nodes.iter().copied().collect();
-    let mut empty: Vec<IJ> = mdig.nodes.to_vec();
+    let wall: HashSet<IJ> = mdig_reverse.nodes().iter().copied().collect();
+    let mut empty: Vec<IJ> = mdig.nodes().to_vec();
     empty.sort();
     assert_eq!(
         wall.len() + empty.len(),
@@ -64,7 +94,12 @@ fn prob2(input: &[&str], max_dist: isize, min_save: usize) -> usize {
     let sdists = mdig.distances_from(m.start);
     let edists = mdig.distances_from(m.end);
     let mut ret = 0usize;
+    // where the cheats start, only when drawing them
+    let mut starts: Vec<IJ> = vec![];
     for &ij in empty.iter() {
+        if cancel::requested() {
+            break;
+        }
         for kl in ij.closer_than_and_bounded(max_dist, minij, maxij) {
             if wall.contains(&kl) {
                 continue;
@@ -80,23 +115,64 @@ fn prob2(input: &[&str], max_dist: isize, min_save: usize) -> usize {
             let dtot = dsij + drev + dkle;
             if dtot + min_save <= d0 {
                 ret += 1;
+                if visual::active() && starts.last() != Some(&ij) {
+                    starts.push(ij);
+                }
             }
         }
     }
+    visual::frame(|| render(input, &starts, 'o'));
     ret
 }
 
-pub(crate) fn main() {
-    let input: Vec<&str> = include_str!("../input/day_20").trim().split("\n").collect();
-    println!("prob1: {}", prob1(&input.clone(), 100));
-    println!("prob2: {}", prob2(&input.clone(), 20, 100));
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Vec<String>;
+    const DAY: usize = 20;
+    const TITLE: &'static str = "Race Condition";
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "min_save",
+            default: 100,
+            help: "picoseconds a cheat must save to be counted",
+        },
+        Param {
+            name: "cheat",
+            default: 20,
+            help: "longest cheat in part 2, in picoseconds",
+        },
+    ];
+    const CONSTRAINTS: &'static [Constraint] = &[
+        Constraint::Rectangular,
+        Constraint::Chars("#.SE"),
+        Constraint::One('S'),
+        Constraint::One('E'),
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, params: &Params) -> Answer {
+        prob1(input, params.get("min_save")).into()
+    }
+
+    fn part2(input: &Self::Input, params: &Params) -> Answer {
+        let cheat = params.get("cheat") as isize;
+        prob2(input, cheat, params.get("min_save")).into()
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
-    use super::{prob1, prob2};
+    use super::{parse, prob1, prob2};
+
+    fn input() -> Vec<String> {
+        parse(&maze().join("\n")).unwrap()
+    }
 
-    fn input() -> Vec<&'static str> {
+    fn maze() -> Vec<&'static str> {
         vec![
             "###############",
             "#...#...#.....#",
@@ -116,7 +192,11 @@ pub mod tests {
         ]
     }
 
-    fn input2() -> Vec<&'static str> {
+    fn input2() -> Vec<String> {
+        parse(&maze2().join("\n")).unwrap()
+    }
+
+    fn maze2() -> Vec<&'static str> {
         vec![
             "#########################################",
             "#...#.............#.....#.....#.....#...#",
@@ -162,6 +242,16 @@ pub mod tests {
         ]
     }
 
+    #[test]
+    fn test_parse() {
+        let mut twice = maze();
+        twice[1] = "#S..#...#.....#";
+        let e = parse(&twice.join("\n")).unwrap_err();
+        assert_eq!((e.line, e.column), (4, 2));
+        let e = parse(&maze()[..3].join("\n")).unwrap_err();
+        assert_eq!((e.line, e.expected.as_str()), (4, "a start `S`"));
+    }
+
     #[test]
     fn test_prob1() {
         assert_eq!(prob1(&input(), 1), 44);
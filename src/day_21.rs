@@ -1,5 +1,13 @@
 use std::collections::{HashMap, HashSet};
 
+use crate::{
+    answer::Answer,
+    cancel,
+    parse::{lines, ParseError},
+    solution::{Param, Params, Solution},
+    utils::{Direction, IJ},
+};
+
 #[derive(Hash, Eq, PartialEq, Debug)]
 struct SeqIter {
     sequence: String,
@@ -49,19 +57,20 @@ impl Lengths {
 }
 
 struct Pad {
-    symbols: HashMap<char, (isize, isize)>,
-    forbidden: HashSet<(isize, isize)>,
+    symbols: HashMap<char, IJ>,
+    forbidden: HashSet<IJ>,
 }
 impl Pad {
     fn from(lines: &[&str]) -> Self {
-        let mut symbols: HashMap<char, (isize, isize)> = HashMap::new();
-        let mut forbidden: HashSet<(isize, isize)> = HashSet::new();
+        let mut symbols: HashMap<char, IJ> = HashMap::new();
+        let mut forbidden: HashSet<IJ> = HashSet::new();
         for (i, &line) in lines.iter().enumerate() {
             for (j, chr) in line.chars().enumerate() {
+                let ij = IJ::from((i as isize, j as isize));
                 if chr != ' ' {
-                    symbols.insert(chr, (i as isize, j as isize));
+                    symbols.insert(chr, ij);
                 } else {
-                    forbidden.insert((i as isize, j as isize));
+                    forbidden.insert(ij);
                 }
             }
         }
@@ -72,12 +81,12 @@ impl Pad {
         let &current = self.symbols.get(&from).unwrap();
         let &target = self.symbols.get(&to).unwrap();
         let horizontal = vec![
-            if target.1 > current.1 { '>' } else { '<' };
-            (target.1 - current.1).unsigned_abs()
+            if target.j > current.j { '>' } else { '<' };
+            (target.j - current.j).unsigned_abs()
         ];
         let vertical = vec![
-            if target.0 > current.0 { 'v' } else { '^' };
-            (target.0 - current.0).unsigned_abs()
+            if target.i > current.i { 'v' } else { '^' };
+            (target.i - current.i).unsigned_abs()
         ];
         if horizontal.is_empty() && vertical.is_empty() {
             return vec!["".to_string()];
@@ -119,12 +128,8 @@ impl Pad {
     fn good_word(&self, word: String) -> bool {
         let mut pos = self.symbols[&'A'];
         for c in word.chars() {
-            match c {
-                '>' => pos = (pos.0, pos.1 + 1),
-                '<' => pos = (pos.0, pos.1 - 1),
-                'v' => pos = (pos.0 + 1, pos.1),
-                '^' => pos = (pos.0 - 1, pos.1),
-                _ => (),
+            if let Some(d) = Direction::from_char(c) {
+                pos = pos + d;
             }
             if self.forbidden.contains(&pos) {
                 return false;
@@ -158,10 +163,24 @@ fn complexity(word: String) -> usize {
         .unwrap()
 }
 
-fn prob1(input: &[&str]) -> usize {
+/// Codes are three digits and an `A`.
+fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    lines(input)
+        .iter()
+        .map(|line| match line.text.strip_suffix('A') {
+            Some(digits) if digits.len() == 3 && digits.chars().all(|c| c.is_ascii_digit()) => {
+                Ok(line.text.to_string())
+            }
+            _ => Err(line.error("a code like `029A`")),
+        })
+        .collect()
+}
+
+fn prob1(input: &[String]) -> usize {
     input
         .iter()
-        .map(|&w| {
+        .take_while(|_| !cancel::requested())
+        .map(|w| {
             let sil = shortest_instruction(w.to_string(), 2);
             let comp = complexity(w.to_string());
             sil.len() * comp
@@ -169,7 +188,7 @@ fn prob1(input: &[&str]) -> usize {
         .sum()
 }
 
-fn prob2(input: &[&str], max_iteration: usize) -> usize {
+fn prob2(input: &[String], max_iteration: usize) -> usize {
     let (pad0, pad1) = pads();
     let mut lengths = Lengths::with_max_iteration(max_iteration);
     input
@@ -187,20 +206,65 @@ fn prob2(input: &[&str], max_iteration: usize) -> usize {
         .sum()
 }
 
-pub(crate) fn main() {
-    let input: Vec<&str> = include_str!("../input/day_21").trim().split("\n").collect();
-    println!("prob1: {}", prob1(&input.clone()));
-    // equivalent to:
-    // println!("prob1: {}", prob2(&input.clone(), 3));
-    println!("prob2: {}", prob2(&input.clone(), 26));
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Vec<String>;
+    const DAY: usize = 21;
+    const TITLE: &'static str = "Keypad Conundrum";
+    const PARAMS: &'static [Param] = &[Param {
+        name: "pads",
+        default: 26,
+        help: "directional keypads in part 2, counting the one we type on",
+    }];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn check(_input: &Self::Input, params: &Params) -> Result<(), String> {
+        params.positive(&["pads"])
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, params: &Params) -> Answer {
+        // part1 is equivalent to prob2(.., 3)
+        prob2(input, params.get("pads")).into()
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
-    use super::{pads, prob1, prob2, shortest_instruction, Lengths, Pad};
+    use super::{pads, parse, prob1, prob2, shortest_instruction, Day21, Lengths, Pad};
+    use crate::{
+        differential::{assert_agree, shrink_vec, Rng},
+        solution::{Params, Solution},
+    };
+
+    fn input() -> Vec<String> {
+        parse("029A\n980A\n179A\n456A\n379A\n").unwrap()
+    }
 
-    fn input() -> Vec<&'static str> {
-        vec!["029A", "980A", "179A", "456A", "379A"]
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("029A\n98A").unwrap_err().line, 2);
+        assert_eq!(parse("02xA").unwrap_err().text, "02xA");
+        assert!(parse("0291").is_err());
+        assert!(parse("029").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let params =
+            |pads: usize| Params::new(Day21::PARAMS, &[("pads".to_string(), pads)]).unwrap();
+        assert!(Day21::check(&input(), &params(1)).is_ok());
+        assert_eq!(
+            Day21::check(&input(), &params(0)),
+            Err("`pads` must be at least 1".to_string())
+        );
     }
 
     #[test]
@@ -240,4 +304,27 @@ pub mod tests {
     fn test_prob2() {
         assert_eq!(prob2(&input(), 3), 126384);
     }
+
+    #[test]
+    fn test_prob2_as_prob1() {
+        // prob1 tries every way to type a code, which takes seconds for some
+        let generate = |rng: &mut Rng| vec![format!("{:03}A", rng.between(0, 999))];
+        // codes with zeros in place of some digits
+        let shrink = |codes: &Vec<String>| {
+            shrink_vec(codes, |code| {
+                (0..3)
+                    .filter(|&n| &code[n..=n] != "0")
+                    .map(|n| format!("{}0{}", &code[..n], &code[n + 1..]))
+                    .collect()
+            })
+        };
+        assert_agree(
+            "prob1 and prob2 with 3 pads",
+            5,
+            generate,
+            shrink,
+            |codes| prob1(codes),
+            |codes| prob2(codes, 3),
+        );
+    }
 }
@@ -1,7 +1,14 @@
 use std::collections::HashMap;
 
-fn parse(input: &[&str]) -> Vec<isize> {
-    input.iter().map(|l| l.parse().unwrap()).collect()
+use crate::{
+    answer::Answer,
+    cancel,
+    parse::{lines, ParseError},
+    solution::{Params, Solution},
+};
+
+fn parse(input: &str) -> Result<Vec<isize>, ParseError> {
+    lines(input).iter().map(|l| l.number(l.text)).collect()
 }
 
 fn next(secret: isize) -> isize {
@@ -40,8 +47,7 @@ fn windows(secret_chain: Vec<isize>) -> impl Iterator<Item = (isize, isize, isiz
     })
 }
 
-fn prob1(input: &[&str]) -> isize {
-    let secrets = parse(input);
+fn prob1(secrets: &[isize]) -> isize {
     secrets.iter().map(|&s| next_nth(s, 2000)).sum()
 }
 
@@ -59,22 +65,38 @@ fn collectable(secret: isize) -> HashMap<(isize, isize, isize, isize), isize> {
     m_collected
 }
 
-fn prob2(input: &[&str]) -> isize {
-    let secrets = parse(input);
+fn prob2(secrets: &[isize]) -> isize {
     let mut collected: HashMap<(isize, isize, isize, isize), isize> = HashMap::new();
-    for secret in secrets {
+    for &secret in secrets {
+        if cancel::requested() {
+            break;
+        }
         let m_collected = collectable(secret);
         m_collected.iter().for_each(|(&w, b)| {
             *collected.entry(w).or_default() += b;
         });
     }
-    *collected.values().max().unwrap()
+    collected.values().max().copied().unwrap_or(0)
 }
 
-pub(crate) fn main() {
-    let input: Vec<&str> = include_str!("../input/day_22").trim().split("\n").collect();
-    println!("prob1: {}", prob1(&input.clone()));
-    println!("prob2: {}", prob2(&input.clone()));
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Vec<isize>;
+    const DAY: usize = 22;
+    const TITLE: &'static str = "Monkey Market";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        prob2(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -82,8 +104,8 @@ pub mod tests {
 
     use super::{next, parse, prob1, prob2};
 
-    fn input() -> Vec<&'static str> {
-        vec!["1", "10", "100", "2024"]
+    fn input() -> Vec<isize> {
+        parse("1\n10\n100\n2024\n").unwrap()
     }
 
     #[test]
@@ -92,7 +114,7 @@ pub mod tests {
             "123", "15887950", "16495136", "527345", "704524", "1553684", "12683156", "11100544",
             "12249484", "7753432", "5908254",
         ];
-        let secrets = parse(&secrets);
+        let secrets = parse(&secrets.join("\n")).unwrap();
         for (&s0, &s1) in secrets.iter().zip(secrets.iter().skip(1)) {
             assert_eq!(next(s0), s1);
         }
@@ -105,7 +127,8 @@ pub mod tests {
 
     #[test]
     fn test_prob2() {
-        let input = vec!["1", "2", "3", "2024"];
+        let input = parse("1\n2\n3\n2024").unwrap();
         assert_eq!(prob2(&input), 23);
+        assert_eq!(parse("1\n2x").unwrap_err().text, "2x");
     }
 }
@@ -1,20 +1,29 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::utils::DiGraph;
-
-fn parse(input: &[&str]) -> DiGraph<String> {
+use crate::{
+    answer::Answer,
+    cancel,
+    parse::{lines, ParseError},
+    solution::{Params, Solution},
+    utils::DiGraph,
+};
+
+fn parse(input: &str) -> Result<DiGraph<String>, ParseError> {
     let mut nodes: HashSet<String> = HashSet::new();
     let mut edges: Vec<(String, String)> = vec![];
 
-    for line in input {
-        let lcomps: Vec<String> = line.split("-").map(|c| c.to_string()).collect();
-        nodes.extend(lcomps.iter().cloned());
-        edges.push((lcomps[0].clone(), lcomps[1].clone()));
-        edges.push((lcomps[1].clone(), lcomps[0].clone()));
+    for line in lines(input) {
+        let (c0, c1) = line.split_once("-")?;
+        if c0.is_empty() || c1.is_empty() || c1.contains('-') {
+            return Err(line.error("two computers joined by `-`"));
+        }
+        nodes.extend([c0.to_string(), c1.to_string()]);
+        edges.push((c0.to_string(), c1.to_string()));
+        edges.push((c1.to_string(), c0.to_string()));
     }
 
     let dg: DiGraph<String> = DiGraph::from(nodes, edges);
-    dg
+    Ok(dg)
 }
 
 impl<I> DiGraph<I> {
@@ -22,12 +31,10 @@ impl<I> DiGraph<I> {
     where
         I: Eq + std::hash::Hash + Clone,
     {
-        let mut ret: HashMap<I, HashSet<I>> = HashMap::new();
-        for (e1, e2) in self.edges.clone() {
-            ret.entry(e1).or_default().insert(e2);
-        }
-
-        ret
+        self.nodes()
+            .iter()
+            .map(|n| (n.clone(), self.children(n).cloned().collect()))
+            .collect()
     }
     fn triangles(&self) -> HashSet<(I, I, I)>
     where
@@ -35,7 +42,7 @@ impl<I> DiGraph<I> {
     {
         let mut ret: HashSet<(I, I, I)> = HashSet::new();
         let neighs = self.neighbors();
-        for n in self.nodes.iter() {
+        for n in self.nodes().iter() {
             let mut nneighs: Vec<I> = neighs.get(n).unwrap().iter().cloned().collect();
             nneighs.sort();
             for i in 0..nneighs.len() {
@@ -58,8 +65,7 @@ impl<I> DiGraph<I> {
     }
 }
 
-fn prob1(input: &[&str]) -> usize {
-    let dg = parse(input);
+fn prob1(dg: &DiGraph<String>) -> usize {
     let triangles = dg.triangles();
     triangles
         .iter()
@@ -67,11 +73,10 @@ fn prob1(input: &[&str]) -> usize {
         .count()
 }
 
-fn prob2(input: &[&str]) -> String {
-    let dg = parse(input);
+fn prob2(dg: &DiGraph<String>) -> String {
     let ns = dg.neighbors();
 
-    let mut nodes = dg.nodes.clone();
+    let mut nodes = dg.nodes().to_vec();
     nodes.sort();
     let nodes_id: HashMap<String, usize> = nodes
         .iter()
@@ -92,6 +97,9 @@ fn cliques_from(
     neighbors: &HashMap<usize, HashSet<usize>>,
     max_clique: &mut Vec<usize>,
 ) -> Vec<usize> {
+    if cancel::requested() {
+        return max_clique.clone();
+    }
     let all_nodes: HashSet<usize> = (0..len).collect();
     let max: isize = if from.is_empty() {
         -1
@@ -121,17 +129,36 @@ fn cliques_from(
     max_clique.clone()
 }
 
-pub(crate) fn main() {
-    let input: Vec<&str> = include_str!("../input/day_23").trim().split("\n").collect();
-    println!("prob1: {}", prob1(&input.clone()));
-    println!("prob2: {}", prob2(&input.clone()));
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = DiGraph<String>;
+    const DAY: usize = 23;
+    const TITLE: &'static str = "LAN Party";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        prob2(input).into()
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
     use super::{parse, prob1, prob2};
+    use crate::utils::DiGraph;
+
+    fn input() -> DiGraph<String> {
+        parse(&connections().join("\n")).unwrap()
+    }
 
-    fn input() -> Vec<&'static str> {
+    fn connections() -> Vec<&'static str> {
         vec![
             "kh-tc", "qp-kh", "de-cg", "ka-co", "yn-aq", "qp-ub", "cg-tb", "vc-aq", "tb-ka",
             "wh-tc", "yn-cg", "kh-ub", "ta-co", "de-co", "tc-td", "tb-wq", "wh-td", "ta-ka",
@@ -142,9 +169,11 @@ pub mod tests {
 
     #[test]
     fn test_triangles() {
-        let dg = parse(&input());
+        let dg = input();
         let triangles = dg.triangles();
         assert_eq!(triangles.len(), 12);
+        assert_eq!(parse("kh-tc\nqp kh").err().unwrap().line, 2);
+        assert!(parse("kh-").is_err());
     }
 
     #[test]
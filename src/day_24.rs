@@ -3,6 +3,12 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use itertools::Itertools;
 use regex::Regex;
 
+use crate::{
+    answer::Answer,
+    parse::{lines, sections, Line, ParseError},
+    solution::{Params, Solution},
+};
+
 #[derive(std::hash::Hash, PartialEq, Eq, Clone, Debug)]
 enum BoolOperator {
     And,
@@ -27,28 +33,24 @@ struct BoolOperation {
 }
 
 impl BoolOperation {
-    fn parse(line: &str) -> Option<(Self, String)> {
-        let opsreg = Regex::new("^([a-z0-9]+) (AND|OR|XOR) ([a-z0-9]+) -> ([a-z0-9]+)$").unwrap();
-        if let Some(opcap) = opsreg.captures(line) {
-            let first = opcap.get(1).unwrap().as_str().to_string();
-            let second = opcap.get(3).unwrap().as_str().to_string();
-            let operator: BoolOperator = match opcap.get(2).unwrap().as_str() {
-                "AND" => BoolOperator::And,
-                "OR" => BoolOperator::Or,
-                "XOR" => BoolOperator::Xor,
-                _ => panic!("wrong operator"),
-            };
-            Some((
-                Self {
-                    first,
-                    operator,
-                    second,
-                },
-                opcap.get(4).unwrap().as_str().to_string(),
-            ))
-        } else {
-            None
-        }
+    fn parse(line: &Line, opsreg: &Regex) -> Result<(Self, String), ParseError> {
+        let opcap = line.captures(opsreg, "a gate like `x00 AND y00 -> z00`")?;
+        let first = opcap.get(1).unwrap().as_str().to_string();
+        let second = opcap.get(3).unwrap().as_str().to_string();
+        let operator: BoolOperator = match opcap.get(2).unwrap().as_str() {
+            "AND" => BoolOperator::And,
+            "OR" => BoolOperator::Or,
+            "XOR" => BoolOperator::Xor,
+            _ => unreachable!("the regex only matches these"),
+        };
+        Ok((
+            Self {
+                first,
+                operator,
+                second,
+            },
+            opcap.get(4).unwrap().as_str().to_string(),
+        ))
     }
     fn result(&self, values: &HashMap<String, bool>) -> bool {
         let first = values[&self.first];
@@ -57,36 +59,45 @@ impl BoolOperation {
     }
 }
 
-struct BoolGraph {
+#[derive(Clone)]
+pub struct BoolGraph {
     nodes: HashSet<String>,
     values: HashMap<String, bool>,
     operations: Vec<(BoolOperation, String)>,
 }
 impl BoolGraph {
-    fn parse(input: &[&str]) -> Self {
+    fn parse(input: &str) -> Result<Self, ParseError> {
         let valreg = Regex::new("^([a-z0-9]+): ([01])$").unwrap();
+        let opsreg = Regex::new("^([a-z0-9]+) (AND|OR|XOR) ([a-z0-9]+) -> ([a-z0-9]+)$").unwrap();
         let mut nodes: HashSet<String> = HashSet::new();
         let mut values: HashMap<String, bool> = HashMap::new();
         let mut operations: Vec<(BoolOperation, String)> = vec![];
-        for line in input {
-            if let Some(valgroups) = valreg.captures(line) {
-                let node = valgroups.get(1).unwrap().as_str().to_string();
-                nodes.insert(node.clone());
-                let valu8: u8 = valgroups.get(2).unwrap().as_str().parse().unwrap();
-                values.insert(node.clone(), valu8 == 1);
-            } else if let Some((operation, target)) = BoolOperation::parse(line) {
-                operations.push((operation.clone(), target.clone()));
-                nodes.insert(target);
-                nodes.insert(operation.first);
-                nodes.insert(operation.second);
-            }
+        let sections = sections(input);
+        let [value_lines, gate_lines] = &sections[..] else {
+            return Err(ParseError::missing(
+                &lines(input),
+                "wire values and gates separated by a blank line",
+            ));
+        };
+        for line in value_lines {
+            let valgroups = line.captures(&valreg, "a wire value like `x00: 1`")?;
+            let node = valgroups.get(1).unwrap().as_str().to_string();
+            nodes.insert(node.clone());
+            values.insert(node, valgroups.get(2).unwrap().as_str() == "1");
+        }
+        for line in gate_lines {
+            let (operation, target) = BoolOperation::parse(line, &opsreg)?;
+            operations.push((operation.clone(), target.clone()));
+            nodes.insert(target);
+            nodes.insert(operation.first);
+            nodes.insert(operation.second);
         }
 
-        Self {
+        Ok(Self {
             nodes,
             values,
             operations,
-        }
+        })
     }
 
     fn end_value(&self) -> usize {
@@ -189,8 +200,8 @@ fn produce_output(bg: &mut BoolGraph) {
     }
 }
 
-fn prob1(input: &[&str]) -> usize {
-    let mut bg = BoolGraph::parse(input);
+fn prob1(input: &BoolGraph) -> usize {
+    let mut bg = input.clone();
     produce_output(&mut bg);
     bg.end_value()
 }
@@ -265,8 +276,8 @@ fn switch(bg: &mut BoolGraph, na: String, nb: String) {
     }
 }
 
-fn prob2(input: &[&str]) -> String {
-    let mut bg = BoolGraph::parse(input);
+fn prob2(input: &BoolGraph) -> String {
+    let mut bg = input.clone();
 
     let mut ret: Vec<String> = vec![];
     let xnodes = bg.nodes_starting_with('x');
@@ -278,8 +289,7 @@ fn prob2(input: &[&str]) -> String {
         if level_has_problem(&mut bg, &xnodes, &ynodes, &znodes, level, false).is_some() {
             let level_to_fix =
                 first_level_not_ok(&mut bg, &xnodes, &ynodes, &znodes, level - 1, level, 2);
-            if level_to_fix.is_some() {
-                let level_to_fix = level_to_fix.unwrap();
+            if let Some(level_to_fix) = level_to_fix {
                 let start = [xnodes[level_to_fix].clone(), ynodes[level_to_fix].clone()];
                 let end = [
                     znodes[level_to_fix].clone(),
@@ -311,22 +321,50 @@ fn prob2(input: &[&str]) -> String {
     ret.join(",")
 }
 
-pub(crate) fn main() {
-    let input: Vec<&str> = include_str!("../input/day_24").trim().split("\n").collect();
-    println!("prob1: {}", prob1(&input.clone()));
-    println!("prob2: {}", prob2(&input.clone()));
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = BoolGraph;
+    const DAY: usize = 24;
+    const TITLE: &'static str = "Crossed Wires";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        BoolGraph::parse(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        prob2(input).into()
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
-    use super::prob1;
+    use super::{prob1, BoolGraph};
 
     #[test]
     fn test_prob1() {
         assert_eq!(prob1(&input()), 2024);
     }
 
-    fn input() -> Vec<&'static str> {
+    #[test]
+    fn test_parse() {
+        let mut wires = wires();
+        wires[12] = "y02 OR x01 => tnw";
+        let e = BoolGraph::parse(&wires.join("\n")).err().unwrap();
+        assert_eq!((e.line, e.text.as_str()), (13, "y02 OR x01 => tnw"));
+        let e = BoolGraph::parse(&wires[..10].join("\n")).err().unwrap();
+        assert_eq!(e.line, 11);
+    }
+
+    fn input() -> BoolGraph {
+        BoolGraph::parse(&wires().join("\n")).unwrap()
+    }
+
+    fn wires() -> Vec<&'static str> {
         vec![
             "x00: 1",
             "x01: 0",
@@ -1,11 +1,19 @@
 use itertools::iproduct;
 
-struct KeyOrLock {
+use crate::{
+    answer::Answer,
+    lint::Constraint,
+    parse::{grid, sections, ParseError},
+    part::Part,
+    solution::{Params, Solution},
+};
+
+pub struct KeyOrLock {
     is_key: bool,
     combination: [u8; 5],
 }
 impl KeyOrLock {
-    fn parse(input: &[&str]) -> Self {
+    fn parse(input: &[String]) -> Self {
         let is_lock = input[0].starts_with('#');
         let mut counter: [u8; 5] = [0; 5];
         for line in input.iter() {
@@ -30,41 +38,69 @@ impl KeyOrLock {
     }
 }
 
-fn parse(input: &[&str]) -> (Vec<KeyOrLock>, Vec<KeyOrLock>) {
+type KeysAndLocks = (Vec<KeyOrLock>, Vec<KeyOrLock>);
+
+/// Blocks of 7 rows of 5, either the top or the bottom row filled.
+fn parse(input: &str) -> Result<KeysAndLocks, ParseError> {
     let mut keys: Vec<KeyOrLock> = vec![];
     let mut locks: Vec<KeyOrLock> = vec![];
-    for batch in input.chunk_by(|&a, &b| a.len() == b.len()) {
-        if batch.len() < 2 {
-            continue;
+    for batch in sections(input) {
+        let rows = grid(&batch, "#.")?;
+        if rows.len() != 7 {
+            return Err(ParseError::missing(&batch, "a block of 7 rows"));
+        }
+        if rows[0].len() != 5 {
+            return Err(batch[0].error("a row of 5 characters"));
+        }
+        if !["#####", "....."].contains(&rows[0].as_str()) || rows[0] == rows[6] {
+            return Err(batch[0].error("a lock or a key"));
         }
-        let kl = KeyOrLock::parse(batch);
+        let kl = KeyOrLock::parse(&rows);
         if kl.is_key {
             keys.push(kl)
         } else {
             locks.push(kl);
         }
     }
-    (keys, locks)
+    Ok((keys, locks))
 }
 
-fn prob1(input: &[&str]) -> usize {
-    let (keys, locks) = parse(input);
+fn prob1((keys, locks): &KeysAndLocks) -> usize {
     iproduct![keys.iter(), locks.iter()]
         .filter(|(k, l)| k.matches(l))
         .count()
 }
 
-pub(crate) fn main() {
-    let input: Vec<&str> = include_str!("../input/day_25").trim().split("\n").collect();
-    println!("prob1: {}", prob1(&input.clone()));
-    println!("prob2: no prob2!");
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = KeysAndLocks;
+    const DAY: usize = 25;
+    const TITLE: &'static str = "Code Chronicle";
+    const PARTS: &'static [Part] = &[Part::One];
+    const CONSTRAINTS: &'static [Constraint] = &[
+        Constraint::BlockLines(7),
+        Constraint::EveryBlock(&[Constraint::Rectangular, Constraint::Chars("#.")]),
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
-    use super::prob1;
+    use super::{parse, prob1, KeysAndLocks};
+
+    fn input() -> KeysAndLocks {
+        parse(&schematics().join("\n")).unwrap()
+    }
 
-    fn input() -> Vec<&'static str> {
+    fn schematics() -> Vec<&'static str> {
         vec![
             "#####", ".####", ".####", ".####", ".#.#.", ".#...", ".....", "", "#####", "##.##",
             ".#.##", "...##", "...#.", "...#.", ".....", "", ".....", "#....", "#....", "#...#",
@@ -77,4 +113,14 @@ pub mod tests {
     fn test_prob1() {
         assert_eq!(prob1(&input()), 3);
     }
+
+    #[test]
+    fn test_parse() {
+        let e = parse(&schematics()[..6].join("\n")).err().unwrap();
+        assert_eq!((e.line, e.expected.as_str()), (7, "a block of 7 rows"));
+        let mut schematics = schematics();
+        schematics[8] = "#.###";
+        let e = parse(&schematics.join("\n")).err().unwrap();
+        assert_eq!((e.line, e.expected.as_str()), (9, "a lock or a key"));
+    }
 }
@@ -1,7 +1,16 @@
 use regex::Regex;
 
+use crate::{
+    answer::Answer,
+    parse::ParseError,
+    solution::{Params, Solution},
+};
+
+/// Numbers in instructions have up to 3 digits, so they always fit.
+const MUL: &str = "mul\\((\\d{1,3}),(\\d{1,3})\\)";
+
 fn prob1(line: &str) -> isize {
-    let re = Regex::new("mul\\((\\d+),(\\d+)\\)").unwrap();
+    let re = Regex::new(MUL).unwrap();
     re.captures_iter(line)
         .map(|c| {
             let (_, [m1, m2]) = c.extract();
@@ -11,8 +20,8 @@ fn prob1(line: &str) -> isize {
 }
 
 fn prob2(line: &str) -> isize {
-    let mulre = Regex::new("mul\\((\\d+),(\\d+)\\)").unwrap();
-    let re = Regex::new("(mul\\((\\d+),(\\d+)\\)|do\\(\\)|don't\\(\\))").unwrap();
+    let mulre = Regex::new(MUL).unwrap();
+    let re = Regex::new(&format!("({MUL}|do\\(\\)|don't\\(\\))")).unwrap();
     let mut adding = true;
     let mut total = 0isize;
     for cap in re.find_iter(line) {
@@ -31,10 +40,25 @@ fn prob2(line: &str) -> isize {
     total
 }
 
-pub(crate) fn main() {
-    let line = include_str!("../input/day_3").trim();
-    println!("prob1: {}", prob1(line));
-    println!("prob2: {}", prob2(line));
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = String;
+    const DAY: usize = 3;
+    const TITLE: &'static str = "Mull It Over";
+
+    /// Anything goes: the memory is corrupted.
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.trim().into())
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        prob2(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -58,4 +82,9 @@ pub mod tests {
     fn test_prob_2() {
         assert_eq!(prob2(input2()), 48);
     }
+
+    #[test]
+    fn test_long_numbers() {
+        assert_eq!(prob1("mul(1234,2)mul(99999999999999999999,1)mul(3,4)"), 12);
+    }
 }
@@ -5,7 +5,13 @@ use std::{
 
 use regex::Regex;
 
-struct Rules {
+use crate::{
+    answer::Answer,
+    parse::{lines, sections, ParseError},
+    solution::{Params, Solution},
+};
+
+pub struct Rules {
     order: HashMap<usize, HashSet<usize>>,
 }
 
@@ -45,26 +51,33 @@ impl Rules {
     }
 }
 
-fn make_rules_and_updates(lines: &[&str]) -> (Rules, Vec<Vec<usize>>) {
+type RulesAndUpdates = (Rules, Vec<Vec<usize>>);
+
+fn make_rules_and_updates(input: &str) -> Result<RulesAndUpdates, ParseError> {
     let rulesre = Regex::new("^(\\d+)\\|(\\d+)$").unwrap();
     let mut rules = Rules::new();
     let mut updates: Vec<Vec<usize>> = vec![];
 
-    for line in lines {
-        if rulesre.is_match(line) {
-            let c = rulesre.captures(line).unwrap();
-            let from: usize = c.get(1).unwrap().as_str().parse().unwrap();
-            let to: usize = c.get(2).unwrap().as_str().parse().unwrap();
-            rules.add(from, to);
-        } else if !line.is_empty() {
-            updates.push(line.split(",").map(|n| n.parse().unwrap()).collect())
-        }
+    let sections = sections(input);
+    let [rule_lines, update_lines] = &sections[..] else {
+        return Err(ParseError::missing(
+            &lines(input),
+            "rules and updates separated by a blank line",
+        ));
+    };
+    for line in rule_lines {
+        let c = line.captures(&rulesre, "a rule like `47|53`")?;
+        let from: usize = line.number(c.get(1).unwrap().as_str())?;
+        let to: usize = line.number(c.get(2).unwrap().as_str())?;
+        rules.add(from, to);
     }
-    (rules, updates)
+    for line in update_lines {
+        updates.push(line.numbers(",")?);
+    }
+    Ok((rules, updates))
 }
 
-fn prob1(lines: &[&str]) -> usize {
-    let (rules, updates) = make_rules_and_updates(lines);
+fn prob1((rules, updates): &RulesAndUpdates) -> usize {
     updates
         .iter()
         .filter(|&u| rules.correct_order(u))
@@ -72,8 +85,7 @@ fn prob1(lines: &[&str]) -> usize {
         .sum()
 }
 
-fn prob2(lines: &[&str]) -> usize {
-    let (rules, updates) = make_rules_and_updates(lines);
+fn prob2((rules, updates): &RulesAndUpdates) -> usize {
     updates
         .iter()
         .filter(|&u| !rules.correct_order(u))
@@ -82,10 +94,24 @@ fn prob2(lines: &[&str]) -> usize {
         .sum()
 }
 
-pub(crate) fn main() {
-    let lines: Vec<&str> = include_str!("../input/day_5").trim().split("\n").collect();
-    println!("prob1: {}", prob1(&lines));
-    println!("prob2: {}", prob2(&lines));
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = RulesAndUpdates;
+    const DAY: usize = 5;
+    const TITLE: &'static str = "Print Queue";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        make_rules_and_updates(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        prob2(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -125,20 +151,31 @@ pub mod tests {
         ]
     }
 
+    fn parsed() -> super::RulesAndUpdates {
+        make_rules_and_updates(&input().join("\n")).unwrap()
+    }
+
     #[test]
     fn test_rules_updates() {
-        let (rules, updates) = make_rules_and_updates(&input());
+        let (rules, updates) = parsed();
         assert_eq!(updates.len(), 6);
         assert_eq!(updates[2], vec![75, 29, 13]);
         assert_eq!(rules.order.len(), 6);
         assert!(rules.is_before(75, 53));
         assert!(!rules.is_before(5, 53));
         assert!(!rules.is_before(53, 75));
+
+        let e = make_rules_and_updates("47|53\n97-13\n\n75,47")
+            .err()
+            .unwrap();
+        assert_eq!((e.line, e.text.as_str()), (2, "97-13"));
+        let e = make_rules_and_updates("47|53\n").err().unwrap();
+        assert_eq!((e.line, e.column), (2, 1));
     }
 
     #[test]
     fn test_correct_order() {
-        let (rules, updates) = make_rules_and_updates(&input());
+        let (rules, updates) = parsed();
         assert!(rules.correct_order(&updates[0]));
         assert!(rules.correct_order(&updates[1]));
         assert!(rules.correct_order(&updates[2]));
@@ -149,11 +186,11 @@ pub mod tests {
 
     #[test]
     fn test_prob_1() {
-        assert_eq!(prob1(&input()), 143);
+        assert_eq!(prob1(&parsed()), 143);
     }
 
     #[test]
     fn test_prob_2() {
-        assert_eq!(prob2(&input()), 123);
+        assert_eq!(prob2(&parsed()), 123);
     }
 }
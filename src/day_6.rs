@@ -1,33 +1,42 @@
+use crate::{
+    answer::Answer,
+    cancel,
+    lint::Constraint,
+    parse::{grid, lines, only_one, ParseError},
+    solution::{Params, Solution},
+    utils::{Direction, IJ},
+    visual,
+};
+
 #[derive(Clone)]
-struct Maze {
-    position: (isize, isize),
-    direction: u8,
+pub struct Maze {
+    position: IJ,
+    direction: Direction,
     grid: Vec<Vec<u8>>,
 }
 
 impl Maze {
-    fn new(grid_str: &[&str]) -> Self {
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let lines = lines(input);
+        let grid_str = grid(&lines, ".#^")?;
         let mut grid: Vec<Vec<u8>> = vec![];
-        let mut position: (isize, isize) = (0, 0);
-        for (i, line_str) in grid_str.iter().enumerate() {
+        for line_str in grid_str.iter() {
             let mut line: Vec<u8> = vec![];
-            for (j, c) in line_str.chars().enumerate() {
+            for c in line_str.chars() {
                 line.push(match c {
                     '#' => 0x10,
                     '^' => 0x1,
                     _ => 0x0,
                 });
-                if c == '^' {
-                    position = (i as isize, j as isize);
-                }
             }
             grid.push(line);
         }
-        Self {
-            position,
-            direction: 0,
+        let (i, j) = only_one(&lines, '^', "a guard `^`")?;
+        Ok(Self {
+            position: IJ::from((i as isize, j as isize)),
+            direction: Direction::North,
             grid,
-        }
+        })
     }
 
     fn height(&self) -> isize {
@@ -38,26 +47,27 @@ impl Maze {
         self.grid[0].len() as isize
     }
 
-    fn inside(&self, ij: (isize, isize)) -> bool {
-        0 <= ij.0 && ij.0 < self.height() && 0 <= ij.1 && ij.1 < self.width()
+    fn inside(&self, ij: IJ) -> bool {
+        ij.inside(
+            IJ { i: 0, j: 0 },
+            IJ {
+                i: self.height(),
+                j: self.width(),
+            },
+        )
     }
 
-    fn at(&self, ij: (isize, isize)) -> u8 {
-        self.grid[ij.0 as usize][ij.1 as usize]
+    fn at(&self, ij: IJ) -> u8 {
+        self.grid[ij.i as usize][ij.j as usize]
     }
 
-    fn set(&mut self, ij: (isize, isize), new_val: u8) {
-        self.grid[ij.0 as usize][ij.1 as usize] = new_val;
+    fn set(&mut self, ij: IJ, new_val: u8) {
+        self.grid[ij.i as usize][ij.j as usize] = new_val;
     }
 
-    fn dir_vector(&self) -> (isize, isize) {
-        match self.direction {
-            0 => (-1, 0),
-            1 => (0, 1),
-            2 => (1, 0),
-            3 => (0, -1),
-            _ => panic!("wrong direction"),
-        }
+    /// The bit marking a visit heading in the current direction.
+    fn dir_bit(&self) -> u8 {
+        1 << self.direction as u8
     }
     /// return codes:
     ///     0 if already visited in diferent directions or rotating
@@ -65,17 +75,16 @@ impl Maze {
     ///     2 if already visited in same direction => loop
     ///     3 out of the grid
     fn step(&mut self) -> usize {
-        let dir = self.dir_vector();
-        let new_pos = (self.position.0 + dir.0, self.position.1 + dir.1);
+        let new_pos = self.position + self.direction;
         if self.inside(new_pos) {
             if self.at(new_pos) == 0x10 {
-                self.direction = (self.direction + 1) % 4;
+                self.direction = self.direction.turn_right();
                 return 0;
             }
             self.position = new_pos;
             let grid_val = self.at(self.position);
-            if grid_val & (1 << self.direction) == 0 {
-                self.set(self.position, grid_val + (1 << self.direction));
+            if grid_val & self.dir_bit() == 0 {
+                self.set(self.position, grid_val + self.dir_bit());
                 if grid_val & 0xf > 0 {
                     return 0;
                 } else {
@@ -88,6 +97,26 @@ impl Maze {
         3
     }
 
+    /// `#` for obstructions, `X` where the guard has been, and the guard.
+    fn render(&self) -> String {
+        let mut ret = String::new();
+        for (i, row) in self.grid.iter().enumerate() {
+            for (j, &v) in row.iter().enumerate() {
+                ret.push(if IJ::from((i as isize, j as isize)) == self.position {
+                    self.direction.arrow()
+                } else if v == 0x10 {
+                    '#'
+                } else if v & 0xf > 0 {
+                    'X'
+                } else {
+                    '.'
+                });
+            }
+            ret.push('\n');
+        }
+        ret
+    }
+
     fn is_looped(&mut self) -> bool {
         loop {
             let s = self.step();
@@ -101,26 +130,33 @@ impl Maze {
     }
 }
 
-fn prob1(lines: &[&str]) -> usize {
+fn prob1(maze: &Maze) -> usize {
     let mut ret: usize = 1; // starting position count
-    let mut maze = Maze::new(lines);
+    let mut maze = maze.clone();
     loop {
+        let direction = maze.direction;
         let s = maze.step();
         if s == 3 {
             break;
         }
+        if maze.direction != direction {
+            visual::frame(|| maze.render());
+        }
         ret += s;
     }
+    visual::frame(|| maze.render());
     ret
 }
 
-fn prob2(lines: &[&str]) -> usize {
+fn prob2(orig_maze: &Maze) -> usize {
     let mut ret: usize = 0;
-    let orig_maze = Maze::new(lines);
     for i in 0..orig_maze.height() {
+        if cancel::requested() {
+            break;
+        }
         for j in 0..orig_maze.width() {
             let mut maze = orig_maze.clone();
-            maze.set((i, j), 0x10);
+            maze.set(IJ { i, j }, 0x10);
             if maze.is_looped() {
                 ret += 1;
             }
@@ -129,15 +165,35 @@ fn prob2(lines: &[&str]) -> usize {
     ret
 }
 
-pub(crate) fn main() {
-    let lines: Vec<&str> = include_str!("../input/day_6").trim().split("\n").collect();
-    println!("prob1: {}", prob1(&lines));
-    println!("prob2: {}", prob2(&lines));
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Maze;
+    const DAY: usize = 6;
+    const TITLE: &'static str = "Guard Gallivant";
+    const CONSTRAINTS: &'static [Constraint] = &[
+        Constraint::Rectangular,
+        Constraint::Chars(".#^"),
+        Constraint::One('^'),
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Maze::new(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        prob2(input).into()
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
     use super::{prob1, prob2, Maze};
+    use crate::utils::{Direction, IJ};
 
     fn input() -> Vec<&'static str> {
         vec![
@@ -156,7 +212,7 @@ pub mod tests {
 
     #[test]
     fn test_new() {
-        let maze = Maze::new(&input());
+        let maze = Maze::new(&input().join("\n")).unwrap();
         assert_eq!(
             maze.grid
                 .into_iter()
@@ -165,17 +221,22 @@ pub mod tests {
                 .sum::<isize>(),
             8 * 0x10 + 1
         );
-        assert_eq!(maze.position, (6, 4));
-        assert_eq!(maze.direction, 0);
+        assert_eq!(maze.position, IJ { i: 6, j: 4 });
+        assert_eq!(maze.direction, Direction::North);
+
+        let e = Maze::new("..\n.#").err().unwrap();
+        assert_eq!((e.line, e.expected.as_str()), (3, "a guard `^`"));
+        let e = Maze::new(".^\n^#").err().unwrap();
+        assert_eq!((e.line, e.column), (2, 1));
     }
 
     #[test]
     fn test_prob1() {
-        assert_eq!(prob1(&input()), 41);
+        assert_eq!(prob1(&Maze::new(&input().join("\n")).unwrap()), 41);
     }
 
     #[test]
     fn test_prob2() {
-        assert_eq!(prob2(&input()), 6);
+        assert_eq!(prob2(&Maze::new(&input().join("\n")).unwrap()), 6);
     }
 }
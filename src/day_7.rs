@@ -1,17 +1,22 @@
-fn parse(lines: &[&str]) -> Vec<(i64, Vec<i64>)> {
+use crate::{
+    answer::Answer,
+    cancel,
+    parse::{lines, ParseError},
+    solution::{Params, Solution},
+};
+
+fn parse(input: &str) -> Result<Vec<(i64, Vec<i64>)>, ParseError> {
     let mut ret = vec![];
-    for line in lines {
-        let mut res_nrs = line.split(": ");
-        let res: i64 = res_nrs.next().unwrap().parse().unwrap();
-        let nrs: Vec<i64> = res_nrs
-            .next()
-            .unwrap()
+    for line in lines(input) {
+        let (res, nrs) = line.split_once(": ")?;
+        let res: i64 = line.number(res)?;
+        let nrs: Vec<i64> = nrs
             .split(" ")
-            .map(|n| n.parse().unwrap())
-            .collect();
+            .map(|n| line.number(n))
+            .collect::<Result<_, _>>()?;
         ret.push((res, nrs));
     }
-    ret
+    Ok(ret)
 }
 
 fn expressable(eq: (i64, Vec<i64>)) -> bool {
@@ -40,6 +45,9 @@ fn apply(mut ops: i32, nrs: &[i64]) -> i64 {
 fn expressable2(eq: (i64, Vec<i64>)) -> bool {
     let (res, nrs) = eq;
     for ops in 0..(3i32.pow(nrs.len() as u32 - 1)) {
+        if ops % 4096 == 0 && cancel::requested() {
+            return false;
+        }
         if apply2(ops, &nrs) == res {
             return true;
         }
@@ -65,26 +73,38 @@ fn apply2(mut ops: i32, nrs: &[i64]) -> i64 {
     res
 }
 
-fn prob1(lines: &[&str]) -> i64 {
-    let eqs = parse(lines);
+fn prob1(eqs: &[(i64, Vec<i64>)]) -> i64 {
     eqs.iter()
         .filter(|&eq| expressable(eq.clone()))
         .map(|(r, _)| r)
         .sum()
 }
 
-fn prob2(lines: &[&str]) -> i64 {
-    let eqs = parse(lines);
+fn prob2(eqs: &[(i64, Vec<i64>)]) -> i64 {
     eqs.iter()
         .filter(|&eq| expressable2(eq.clone()))
         .map(|(r, _)| r)
         .sum()
 }
 
-pub(crate) fn main() {
-    let lines: Vec<&str> = include_str!("../input/day_7").trim().split("\n").collect();
-    println!("prob1: {}", prob1(&lines));
-    println!("prob2: {}", prob2(&lines));
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<(i64, Vec<i64>)>;
+    const DAY: usize = 7;
+    const TITLE: &'static str = "Bridge Repair";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        prob2(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -105,12 +125,22 @@ pub mod tests {
         ]
     }
 
+    fn parsed() -> Vec<(i64, Vec<i64>)> {
+        parse(&input().join("\n")).unwrap()
+    }
+
     #[test]
     fn test_parse() {
-        let eqs = parse(&input());
+        let eqs = parsed();
         assert_eq!(eqs.len(), 9);
         assert_eq!(eqs[1].0, 3267);
         assert_eq!(eqs[1].1, vec![81, 40, 27]);
+
+        let e = parse("190: 10 19\n3267 81 40").unwrap_err();
+        assert_eq!((e.line, e.expected.as_str()), (2, "`: `"));
+        let e = parse("190: 10 19\n3267: 81 4o").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 10, "4o"));
+        assert_eq!(parse("190: \n3: 3").unwrap_err().column, 6);
     }
 
     #[test]
@@ -123,7 +153,7 @@ pub mod tests {
 
     #[test]
     fn test_expressable() {
-        let eqs = parse(&input());
+        let eqs = parsed();
         assert!(expressable(eqs[0].clone()));
         assert!(expressable(eqs[1].clone()));
         assert!(!expressable(eqs[2].clone()));
@@ -137,7 +167,7 @@ pub mod tests {
 
     #[test]
     fn test_prob1() {
-        assert_eq!(prob1(&input()), 3749);
+        assert_eq!(prob1(&parsed()), 3749);
     }
 
     #[test]
@@ -151,7 +181,7 @@ pub mod tests {
 
     #[test]
     fn test_expressable2() {
-        let eqs = parse(&input());
+        let eqs = parsed();
         let expre: Vec<i64> = eqs
             .iter()
             .filter(|&e| expressable2(e.clone()))
@@ -163,6 +193,6 @@ pub mod tests {
 
     #[test]
     fn test_prob2() {
-        assert_eq!(prob2(&input()), 11387);
+        assert_eq!(prob2(&parsed()), 11387);
     }
 }
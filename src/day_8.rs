@@ -1,35 +1,54 @@
 use std::collections::{HashMap, HashSet};
 
-#[allow(clippy::type_complexity)]
-fn parse(lines: &[&str]) -> (HashMap<char, Vec<(isize, isize)>>, isize, isize) {
+use crate::{
+    answer::Answer,
+    lint::Constraint,
+    parse::{grid, lines, ParseError},
+    solution::{Params, Solution},
+    utils::IJ,
+};
+
+/// Empty, or an antenna of some frequency.
+const CELLS: &str = ".abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// Positions of the antennas of each frequency, and the height and width of the map.
+type Antennas = (HashMap<char, Vec<IJ>>, isize, isize);
+
+fn parse(input: &str) -> Result<Antennas, ParseError> {
+    let lines = grid(&lines(input), CELLS)?;
     let height = lines.len() as isize;
     let width = lines[0].len() as isize;
-    let mut ret: HashMap<char, Vec<(isize, isize)>> = HashMap::new();
+    let mut ret: HashMap<char, Vec<IJ>> = HashMap::new();
     for (i, line) in lines.iter().enumerate() {
         for (j, chr) in line.chars().enumerate() {
             if chr != '.' {
-                ret.entry(chr).or_default().push((i as isize, j as isize));
+                ret.entry(chr)
+                    .or_default()
+                    .push(IJ::from((i as isize, j as isize)));
             }
         }
     }
-    (ret, height, width)
+    Ok((ret, height, width))
 }
 
-fn count_symmetries(
-    antennas: HashMap<char, Vec<(isize, isize)>>,
-    height: isize,
-    width: isize,
-) -> usize {
-    let mut added: HashSet<(isize, isize)> = HashSet::new();
-    for (_, positions) in antennas {
-        for (i, j) in positions.clone() {
-            for (k, l) in positions.clone() {
-                if (k, l) == (i, j) {
+fn count_symmetries(antennas: &HashMap<char, Vec<IJ>>, height: isize, width: isize) -> usize {
+    let (origin, size) = (
+        IJ { i: 0, j: 0 },
+        IJ {
+            i: height,
+            j: width,
+        },
+    );
+    let mut added: HashSet<IJ> = HashSet::new();
+    for positions in antennas.values() {
+        for &a in positions {
+            for &b in positions {
+                if a == b {
                     continue;
                 }
-                let (ni, nj) = (2 * k - i, 2 * l - j);
-                if 0 <= ni && ni < height && 0 <= nj && nj < width {
-                    added.insert((ni, nj));
+                let antinode = 2 * b - a;
+                if antinode.inside(origin, size) {
+                    added.insert(antinode);
                 }
             }
         }
@@ -37,20 +56,23 @@ fn count_symmetries(
     added.len()
 }
 
-fn count_whole_lines(
-    antennas: HashMap<char, Vec<(isize, isize)>>,
-    height: isize,
-    width: isize,
-) -> usize {
-    let mut added: HashSet<(isize, isize)> = HashSet::new();
-    for (_, positions) in antennas {
-        for (i, j) in positions.clone() {
-            for (k, l) in positions.clone() {
-                if (k, l) != (i, j) {
+fn count_whole_lines(antennas: &HashMap<char, Vec<IJ>>, height: isize, width: isize) -> usize {
+    let (origin, size) = (
+        IJ { i: 0, j: 0 },
+        IJ {
+            i: height,
+            j: width,
+        },
+    );
+    let mut added: HashSet<IJ> = HashSet::new();
+    for positions in antennas.values() {
+        for &a in positions {
+            for &b in positions {
+                if a != b {
                     for t in 1.. {
-                        let (ni, nj) = (i + t * (k - i), j + t * (l - j));
-                        if 0 <= ni && ni < height && 0 <= nj && nj < width {
-                            added.insert((ni, nj));
+                        let antinode = a + t * (b - a);
+                        if antinode.inside(origin, size) {
+                            added.insert(antinode);
                         } else {
                             break;
                         }
@@ -62,25 +84,39 @@ fn count_whole_lines(
     added.len()
 }
 
-fn prob1(lines: &[&str]) -> usize {
-    let (g, h, w) = parse(lines);
-    count_symmetries(g, h, w)
+fn prob1((g, h, w): &Antennas) -> usize {
+    count_symmetries(g, *h, *w)
 }
 
-fn prob2(lines: &[&str]) -> usize {
-    let (g, h, w) = parse(lines);
-    count_whole_lines(g, h, w)
+fn prob2((g, h, w): &Antennas) -> usize {
+    count_whole_lines(g, *h, *w)
 }
 
-pub(crate) fn main() {
-    let lines: Vec<&str> = include_str!("../input/day_8").trim().split("\n").collect();
-    println!("prob1: {}", prob1(&lines));
-    println!("prob2: {}", prob2(&lines));
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Antennas;
+    const DAY: usize = 8;
+    const TITLE: &'static str = "Resonant Collinearity";
+    const CONSTRAINTS: &'static [Constraint] = &[Constraint::Rectangular, Constraint::Chars(CELLS)];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        prob2(input).into()
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
     use super::{parse, prob1, prob2};
+    use crate::utils::IJ;
 
     fn input() -> Vec<&'static str> {
         vec![
@@ -101,20 +137,22 @@ pub mod tests {
 
     #[test]
     fn test_prob1() {
-        assert_eq!(prob1(&input()), 14);
+        assert_eq!(prob1(&parse(&input().join("\n")).unwrap()), 14);
     }
 
     #[test]
     fn test_prob2() {
-        assert_eq!(prob2(&input()), 34);
+        assert_eq!(prob2(&parse(&input().join("\n")).unwrap()), 34);
     }
 
     #[test]
     fn test_parse() {
-        let (g, h, w) = parse(&input());
+        let (g, h, w) = parse(&input().join("\n")).unwrap();
         assert_eq!(g.len(), 2);
-        assert_eq!(g[&'0'], vec![(1, 8), (2, 5), (3, 7), (4, 4)]);
+        let zeros: Vec<IJ> = [(1, 8), (2, 5), (3, 7), (4, 4)].map(IJ::from).to_vec();
+        assert_eq!(g[&'0'], zeros);
         assert_eq!(h, 12);
         assert_eq!(w, 12);
+        assert_eq!(parse("..\n.#").unwrap_err().text, "#");
     }
 }
@@ -1,14 +1,32 @@
 use std::collections::{BTreeSet, HashMap};
 
-fn parse(line: String) -> Vec<isize> {
+use crate::{
+    answer::Answer,
+    cancel,
+    parse::{single_line, ParseError},
+    solution::{Params, Solution},
+};
+
+/// The disk map, one length per digit.
+fn digits(input: &str) -> Result<Vec<usize>, ParseError> {
+    let line = single_line(input)?;
+    line.only("0123456789")?;
+    Ok(line
+        .text
+        .chars()
+        .map(|c| c as usize - '0' as usize)
+        .collect())
+}
+
+fn parse(line: &[usize]) -> Vec<isize> {
     let mut ret: Vec<isize> = Vec::with_capacity(20 * line.len());
     for i in (0..line.len()).step_by(2) {
-        let a: usize = line[i..i + 1].parse().unwrap();
+        let a = line[i];
         for _ in 0..a {
             ret.push((i / 2) as isize);
         }
         if i + 1 < line.len() {
-            let b: usize = line[i + 1..i + 2].parse().unwrap();
+            let b = line[i + 1];
             for _ in 0..b {
                 ret.push(-1);
             }
@@ -32,7 +50,7 @@ fn compact(disk: &mut Vec<isize>) {
     }
 }
 
-fn prob1(line: String) -> usize {
+fn prob1(line: &[usize]) -> usize {
     let mut disk = parse(line);
     compact(&mut disk);
     disk.iter()
@@ -48,16 +66,16 @@ struct Files {
 }
 
 impl Files {
-    fn from_string(line: String) -> Self {
+    fn from_digits(line: &[usize]) -> Self {
         let mut files: Vec<(usize, usize, isize)> = vec![];
         let mut holes: HashMap<usize, BTreeSet<usize>> = HashMap::new();
         let mut pos: usize = 0;
         for i in (0..line.len()).step_by(2) {
-            let a: usize = line[i..i + 1].parse().unwrap();
+            let a = line[i];
             files.push((pos, a, i as isize / 2));
             pos += a;
             if i + 1 < line.len() {
-                let b: usize = line[i + 1..i + 2].parse().unwrap();
+                let b = line[i + 1];
                 holes.entry(b).or_default().insert(pos);
                 pos += b;
             }
@@ -80,10 +98,12 @@ impl Files {
     fn compact(&mut self) {
         let mut file_vec_pos = self.files.len() - 1;
         while file_vec_pos > 0 {
+            if cancel::requested() {
+                break;
+            }
             let (pos, len, id) = self.files[file_vec_pos];
             let leftmost_holes = self.lmh(pos, len);
-            if leftmost_holes.is_some() {
-                let hlen = leftmost_holes.unwrap();
+            if let Some(hlen) = leftmost_holes {
                 let hpos = *self.holes.get(&hlen).unwrap().first().unwrap();
                 self.holes.entry(hlen).and_modify(|hp| {
                     hp.pop_first();
@@ -120,42 +140,58 @@ impl Files {
     }
 }
 
-fn prob2(line: String) -> usize {
-    let mut files = Files::from_string(line);
+fn prob2(line: &[usize]) -> usize {
+    let mut files = Files::from_digits(line);
     files.compact();
     files.checksum()
 }
 
-pub(crate) fn main() {
-    let line = include_str!("../input/day_9").trim().to_string();
-    println!("prob1: {}", prob1(line.clone()));
-    println!("prob2: {}", prob2(line.clone()));
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<usize>;
+    const DAY: usize = 9;
+    const TITLE: &'static str = "Disk Fragmenter";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        digits(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        prob2(input).into()
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
     use std::collections::BTreeSet;
 
-    use super::{compact, parse, prob1, Files};
+    use super::{compact, digits, parse, prob1, Files};
 
-    fn input() -> String {
-        "2333133121414131402".to_string()
+    fn input() -> Vec<usize> {
+        digits("2333133121414131402\n").unwrap()
     }
 
     #[test]
     fn test_prob1() {
-        assert_eq!(prob1(input()), 1928);
+        assert_eq!(prob1(&input()), 1928);
     }
 
     #[test]
     fn test_parse() {
-        let d = parse(input());
+        let d = parse(&input());
         assert_eq!(d.len(), 42);
+        let e = digits("23331-3").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (6, "-"));
     }
 
     #[test]
     fn test_compact() {
-        let mut d = parse(input());
+        let mut d = parse(&input());
         compact(&mut d);
         assert_eq!(
             d,
@@ -167,7 +203,7 @@ pub mod tests {
 
     #[test]
     fn test_files() {
-        let mut files = Files::from_string(input());
+        let mut files = Files::from_digits(&input());
         assert_eq!(files.files.len(), 10);
         assert_eq!(files.files.last().unwrap(), &(40, 2, 9));
         assert_eq!(files.holes[&3], BTreeSet::from([2, 8, 12]));
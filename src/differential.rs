@@ -0,0 +1,176 @@
+use std::{env, fmt::Debug};
+
+/// Environment variable with the seed of the random inputs, to try other
+/// ones than the default.
+pub const SEED_VAR: &str = "AOC_DIFF_SEED";
+pub const DEFAULT_SEED: u64 = 2024;
+
+/// Small random number generator (splitmix64). Good enough for making up
+/// inputs, and the same for a seed everywhere.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `lo..=hi`.
+    pub fn between(&mut self, lo: usize, hi: usize) -> usize {
+        lo + (self.next_u64() % (hi - lo + 1) as u64) as usize
+    }
+
+    /// True once in `n` times or so.
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.between(1, n) == 1
+    }
+}
+
+/// An input on which two implementations differ, as small as it got.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement<T, R> {
+    pub seed: u64,
+    pub input: T,
+    pub expected: R,
+    pub got: R,
+}
+
+/// The first of `cases` inputs made by `generate` from `seed` on which
+/// `reference` and `other` differ, shrunk while they keep differing: of the
+/// smaller inputs `shrink` gives, the first that still fails is taken, until
+/// none does.
+pub fn disagreement<T, R>(
+    seed: u64,
+    cases: usize,
+    generate: impl Fn(&mut Rng) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    reference: impl Fn(&T) -> R,
+    other: impl Fn(&T) -> R,
+) -> Option<Disagreement<T, R>>
+where
+    R: PartialEq,
+{
+    let mut rng = Rng::new(seed);
+    let differ = |input: &T| {
+        let (expected, got) = (reference(input), other(input));
+        (expected != got).then_some((expected, got))
+    };
+    let mut input = (0..cases)
+        .map(|_| generate(&mut rng))
+        .find(|i| differ(i).is_some())?;
+    while let Some(smaller) = shrink(&input).into_iter().find(|i| differ(i).is_some()) {
+        input = smaller;
+    }
+    let (expected, got) = differ(&input).unwrap();
+    Some(Disagreement {
+        seed,
+        input,
+        expected,
+        got,
+    })
+}
+
+/// Panics with the smallest input found on which `reference` and `other`
+/// differ. The seed is `$AOC_DIFF_SEED`, or `DEFAULT_SEED`.
+pub fn assert_agree<T, R>(
+    name: &str,
+    cases: usize,
+    generate: impl Fn(&mut Rng) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    reference: impl Fn(&T) -> R,
+    other: impl Fn(&T) -> R,
+) where
+    T: Debug,
+    R: PartialEq + Debug,
+{
+    let seed = match env::var(SEED_VAR) {
+        Ok(seed) => seed
+            .parse()
+            .unwrap_or_else(|_| panic!("{SEED_VAR} must be a number, not `{seed}`")),
+        Err(_) => DEFAULT_SEED,
+    };
+    if let Some(d) = disagreement(seed, cases, generate, shrink, reference, other) {
+        panic!(
+            "{name} disagree with seed {}: expected {:?}, got {:?} for {:?}",
+            d.seed, d.expected, d.got, d.input
+        );
+    }
+}
+
+/// `n` made smaller: 0, half of it, and one less.
+pub fn shrink_usize(n: usize) -> Vec<usize> {
+    let mut ret = vec![0, n / 2, n.saturating_sub(1)];
+    ret.dedup();
+    ret.retain(|&m| m < n);
+    ret
+}
+
+/// `v` without one of its elements, or with one of them shrunk.
+pub fn shrink_vec<T: Clone>(v: &[T], shrink: impl Fn(&T) -> Vec<T>) -> Vec<Vec<T>> {
+    let mut ret = vec![];
+    for n in 0..v.len() {
+        let mut shorter = v.to_vec();
+        shorter.remove(n);
+        ret.push(shorter);
+    }
+    for (n, x) in v.iter().enumerate() {
+        for smaller in shrink(x) {
+            let mut changed = v.to_vec();
+            changed[n] = smaller;
+            ret.push(changed);
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::{disagreement, shrink_usize, shrink_vec, Rng};
+
+    #[test]
+    fn test_rng() {
+        let mut rng = Rng::new(1);
+        let ns: Vec<usize> = (0..1000).map(|_| rng.between(3, 5)).collect();
+        assert!(ns.iter().all(|n| (3..=5).contains(n)));
+        assert!((3..=5).all(|n| ns.contains(&n)));
+        let mut again = Rng::new(1);
+        assert_eq!(again.between(3, 5), ns[0]);
+    }
+
+    #[test]
+    fn test_shrink() {
+        assert_eq!(shrink_usize(10), vec![0, 5, 9]);
+        assert_eq!(shrink_usize(1), vec![0]);
+        assert!(shrink_usize(0).is_empty());
+        assert_eq!(
+            shrink_vec(&[2, 1], |&n| shrink_usize(n)),
+            vec![vec![1], vec![2], vec![0, 1], vec![1, 1], vec![2, 0]]
+        );
+    }
+
+    #[test]
+    fn test_disagreement() {
+        let sum = |v: &Vec<usize>| v.iter().sum::<usize>();
+        // wrong when there is something over 20
+        let capped = |v: &Vec<usize>| v.iter().map(|&n| n.min(20)).sum::<usize>();
+        let generate = |rng: &mut Rng| {
+            let len = rng.between(0, 8);
+            (0..len)
+                .map(|_| rng.between(0, 100))
+                .collect::<Vec<usize>>()
+        };
+        let shrink = |v: &Vec<usize>| shrink_vec(v, |&n| shrink_usize(n));
+        let d = disagreement(7, 100, generate, shrink, sum, capped).unwrap();
+        assert_eq!(d.input, vec![21]);
+        assert_eq!((d.expected, d.got), (21, 20));
+        assert!(disagreement(7, 100, generate, shrink, sum, sum).is_none());
+    }
+}
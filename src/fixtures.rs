@@ -0,0 +1,304 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    answers::{is_int, unquote, AnswersError},
+    lint,
+    part::Part,
+    registry::Registry,
+    runner::run_day,
+    solution::{day_label, Params, DEFAULT_YEAR},
+};
+
+/// Directory with the examples, relative to the crate root.
+pub const DEFAULT_DIR: &str = "fixtures";
+
+/// What an example should give, and the parameters it needs, in the same
+/// small subset of TOML as `answers.toml` but without tables:
+///
+/// ```toml
+/// part1 = 12
+/// width = 11
+/// height = 7
+/// ```
+///
+/// Every key other than `part1` and `part2` is a parameter.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Expected {
+    pub answers: BTreeMap<Part, String>,
+    pub params: Vec<(String, usize)>,
+}
+
+impl Expected {
+    pub fn parse(text: &str) -> Result<Self, AnswersError> {
+        let mut ret = Expected::default();
+        for (n, line) in text.lines().enumerate() {
+            let syntax = |message: String| AnswersError::Syntax {
+                line: n + 1,
+                message,
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(syntax(format!("expected `key = value`, found `{line}`")));
+            };
+            let (key, value) = (key.trim(), value.trim());
+            if let Some(part) = key.strip_prefix("part") {
+                let part: Part = part.parse().map_err(syntax)?;
+                let answer = if is_int(value) {
+                    value.to_string()
+                } else {
+                    unquote(value).ok_or_else(|| syntax(format!("can't read answer `{value}`")))?
+                };
+                if ret.answers.insert(part, answer).is_some() {
+                    return Err(syntax(format!("part {part} given twice")));
+                }
+            } else {
+                let value = value
+                    .parse()
+                    .map_err(|_| syntax(format!("`{key}` must be a non-negative integer")))?;
+                ret.params.push((key.to_string(), value));
+            }
+        }
+        Ok(ret)
+    }
+}
+
+/// An example input from the puzzle text: `DIR/day_N/NAME.txt`, or
+/// `DIR/YEAR/day_N/NAME.txt` for other years, with what it should give in
+/// `NAME.toml` next to it.
+#[derive(Debug)]
+pub struct Fixture {
+    pub year: usize,
+    pub day: usize,
+    pub name: String,
+    pub input: String,
+    pub expected: Expected,
+}
+
+impl fmt::Display for Fixture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", day_label(self.year, self.day), self.name)
+    }
+}
+
+#[derive(Debug)]
+pub struct FixtureError {
+    pub path: PathBuf,
+    pub error: AnswersError,
+}
+
+impl fmt::Display for FixtureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.error)
+    }
+}
+
+impl std::error::Error for FixtureError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+fn read(path: &Path) -> Result<String, FixtureError> {
+    fs::read_to_string(path).map_err(|e| FixtureError {
+        path: path.to_path_buf(),
+        error: AnswersError::Io(e),
+    })
+}
+
+/// Sorted names of the entries in `dir` for which `keep` gives something.
+fn entries<T: Ord>(
+    dir: &Path,
+    keep: impl Fn(&Path) -> Option<T>,
+) -> Result<Vec<(T, PathBuf)>, FixtureError> {
+    let io_error = |e: io::Error| FixtureError {
+        path: dir.to_path_buf(),
+        error: AnswersError::Io(e),
+    };
+    let mut ret = vec![];
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if let Some(key) = keep(&path) {
+            ret.push((key, path));
+        }
+    }
+    ret.sort();
+    Ok(ret)
+}
+
+/// The `day_N` directories in `dir`, by day.
+fn day_dirs(dir: &Path) -> Result<Vec<(usize, PathBuf)>, FixtureError> {
+    entries(dir, |path| {
+        let name = path.file_name()?.to_str()?;
+        let day: usize = name.strip_prefix("day_")?.parse().ok()?;
+        path.is_dir().then_some(day)
+    })
+}
+
+/// Every fixture in `dir`, by year, day and then name. Other files are
+/// ignored, but an input without its `.toml` is an error.
+pub fn discover(dir: &Path) -> Result<Vec<Fixture>, FixtureError> {
+    let years = entries(dir, |path| {
+        let year: usize = path.file_name()?.to_str()?.parse().ok()?;
+        path.is_dir().then_some(year)
+    })?;
+    let mut days: Vec<(usize, usize, PathBuf)> = day_dirs(dir)?
+        .into_iter()
+        .map(|(day, path)| (DEFAULT_YEAR, day, path))
+        .collect();
+    for (year, year_dir) in years {
+        days.extend(
+            day_dirs(&year_dir)?
+                .into_iter()
+                .map(|(day, path)| (year, day, path)),
+        );
+    }
+    days.sort();
+    let mut ret = vec![];
+    for (year, day, day_dir) in days {
+        let inputs = entries(&day_dir, |path| {
+            let name = path.file_stem()?.to_str()?;
+            (path.extension()? == "txt").then(|| name.to_string())
+        })?;
+        for (name, path) in inputs {
+            let toml = path.with_extension("toml");
+            let expected = Expected::parse(&read(&toml)?).map_err(|error| FixtureError {
+                path: toml.clone(),
+                error,
+            })?;
+            ret.push(Fixture {
+                year,
+                day,
+                name,
+                input: read(&path)?,
+                expected,
+            });
+        }
+    }
+    Ok(ret)
+}
+
+/// Solves `fixture` and says what went wrong, if anything, including what
+/// its input breaks of the day's constraints.
+pub fn check(registry: &Registry, fixture: &Fixture) -> Vec<String> {
+    let Some(solver) = registry.get(fixture.year, fixture.day) else {
+        return vec![format!("{fixture}: no such day")];
+    };
+    let parts: Vec<Part> = fixture.expected.answers.keys().copied().collect();
+    if parts.is_empty() {
+        return vec![format!("{fixture}: no answers to check")];
+    }
+    let missing: Vec<String> = parts
+        .iter()
+        .filter(|p| !solver.parts().contains(p))
+        .map(|p| format!("{fixture}: the day has no part {p}"))
+        .collect();
+    if !missing.is_empty() {
+        return missing;
+    }
+    let lint = lint::check(solver.constraints(), &fixture.input);
+    if !lint.is_empty() {
+        return lint.iter().map(|e| format!("{fixture}: {e}")).collect();
+    }
+    let params = match Params::new(solver.params(), &fixture.expected.params) {
+        Ok(params) => params,
+        Err(e) => return vec![format!("{fixture}: {e}")],
+    };
+    let run = match run_day(solver, &fixture.input, &parts, &params, None) {
+        Ok(run) => run,
+        Err(e) => return vec![format!("{fixture}: {e}")],
+    };
+    run.parts
+        .iter()
+        .filter_map(|p| {
+            let expected = &fixture.expected.answers[&p.part];
+            let got = p.answer.to_string();
+            (got != *expected)
+                .then(|| format!("{fixture} part {}: expected {expected}, got {got}", p.part))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::fs;
+
+    use super::{check, discover, Expected, Fixture};
+    use crate::{answers::AnswersError, part::Part, registry};
+
+    #[test]
+    fn test_expected() {
+        let expected =
+            Expected::parse("# 6 blinks\npart1 = 22\nblinks1 = 6\npart2 = \"a,b\"\n").unwrap();
+        assert_eq!(expected.answers[&Part::One], "22");
+        assert_eq!(expected.answers[&Part::Two], "a,b");
+        assert_eq!(expected.params, vec![("blinks1".to_string(), 6)]);
+
+        let line_of = |text: &str| match Expected::parse(text) {
+            Err(AnswersError::Syntax { line, .. }) => line,
+            other => panic!("{other:?} is not a syntax error"),
+        };
+        assert_eq!(line_of("part3 = 1"), 1);
+        assert_eq!(line_of("part1 = 1\nwidth = -1"), 2);
+        let zero = Expected::parse("min_save = 0").unwrap();
+        assert_eq!(zero.params, vec![("min_save".to_string(), 0)]);
+        assert_eq!(line_of("part1 = 1\npart1 = 1"), 2);
+        assert_eq!(line_of("[day_1]"), 1);
+    }
+
+    #[test]
+    fn test_discover() {
+        let dir =
+            std::env::temp_dir().join(format!("aoc_2024_fixtures_test_{}", std::process::id()));
+        let day_11 = dir.join("day_11");
+        fs::create_dir_all(&day_11).unwrap();
+        fs::write(day_11.join("short.txt"), "125 17\n").unwrap();
+        fs::write(day_11.join("short.toml"), "part1 = 22\nblinks1 = 6\n").unwrap();
+        fs::write(day_11.join("example.txt"), "125 17\n").unwrap();
+        fs::write(day_11.join("example.toml"), "part1 = 55312\npart2 = 1\n").unwrap();
+        fs::write(day_11.join("notes.md"), "not a fixture").unwrap();
+        let old = dir.join("2023").join("day_1");
+        fs::create_dir_all(&old).unwrap();
+        fs::write(old.join("example.txt"), "1abc2\n").unwrap();
+        fs::write(old.join("example.toml"), "part1 = 12\n").unwrap();
+        let fixtures = discover(&dir).unwrap();
+        fs::write(day_11.join("orphan.txt"), "1\n").unwrap();
+        let orphan = discover(&dir).unwrap_err();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<String> = fixtures.iter().map(|f| f.to_string()).collect();
+        assert_eq!(
+            names,
+            vec!["2023 day 1 example", "day 11 example", "day 11 short"]
+        );
+        assert!(orphan.path.ends_with("orphan.toml"));
+
+        let registry = registry::all();
+        assert_eq!(
+            check(&registry, &fixtures[0]),
+            vec!["2023 day 1 example: no such day"]
+        );
+        let problems = check(&registry, &fixtures[1]);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("day 11 example part 2: expected 1, got "));
+        assert!(check(&registry, &fixtures[2]).is_empty());
+
+        let last = Fixture {
+            year: 2024,
+            day: 25,
+            name: "example".to_string(),
+            input: String::new(),
+            expected: Expected::parse("part1 = 3\npart2 = 1\n").unwrap(),
+        };
+        assert_eq!(
+            check(&registry, &last),
+            vec!["day 25 example: the day has no part 2"]
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use std::{
+    env, fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::solution::{day_label, DEFAULT_YEAR};
+
+/// Directory where inputs are looked up when nothing else is said.
+pub const DEFAULT_DIR: &str = "input";
+/// Environment variable that, when set, replaces `DEFAULT_DIR`.
+pub const DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where to read the input of a day from.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Source {
+    /// `$AOC_INPUT_DIR/day_N`, or `input/day_N` if the variable is not set.
+    /// Days of other years than the default one are in a directory named
+    /// after the year, as in `input/2023/day_N`.
+    #[default]
+    Default,
+    /// this exact file, whatever the day
+    File(PathBuf),
+    /// `day_N` inside this directory, or inside its `YEAR` directory
+    Dir(PathBuf),
+    /// standard input
+    Stdin,
+}
+
+impl Source {
+    /// `-` means stdin, an existing directory means `Dir`, anything else is a file.
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            return Self::Stdin;
+        }
+        let path = PathBuf::from(arg);
+        if path.is_dir() {
+            Self::Dir(path)
+        } else {
+            Self::File(path)
+        }
+    }
+
+    fn path(&self, year: usize, day: usize) -> Option<PathBuf> {
+        match self {
+            Self::Default => {
+                let dir = env::var_os(DIR_VAR)
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| PathBuf::from(DEFAULT_DIR));
+                Some(day_path(&dir, year, day))
+            }
+            Self::File(path) => Some(path.clone()),
+            Self::Dir(dir) => Some(day_path(dir, year, day)),
+            Self::Stdin => None,
+        }
+    }
+}
+
+/// `DIR/day_N`, or `DIR/YEAR/day_N` for other years than the default one.
+pub(crate) fn day_path(dir: &Path, year: usize, day: usize) -> PathBuf {
+    let name = format!("day_{day}");
+    if year == DEFAULT_YEAR {
+        dir.join(name)
+    } else {
+        dir.join(year.to_string()).join(name)
+    }
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    pub year: usize,
+    pub day: usize,
+    /// the file we tried to read, `None` for stdin
+    pub path: Option<PathBuf>,
+    pub error: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.path {
+            Some(path) => write!(
+                f,
+                "can't read input for {} from {}: {}",
+                day_label(self.year, self.day),
+                path.display(),
+                self.error
+            ),
+            None => write!(
+                f,
+                "can't read input for {} from stdin: {}",
+                day_label(self.year, self.day),
+                self.error
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Reads the whole input of `day` of `year` from `source`.
+pub fn load(year: usize, day: usize, source: &Source) -> Result<String, InputError> {
+    let path = source.path(year, day);
+    let read = match &path {
+        Some(path) => fs::read_to_string(path),
+        None => {
+            let mut buf = String::new();
+            io::stdin().read_to_string(&mut buf).map(|_| buf)
+        }
+    };
+    read.map_err(|error| InputError {
+        year,
+        day,
+        path,
+        error,
+    })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::{env, fs, io, path::PathBuf, process};
+
+    use super::{load, Source};
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(Source::from_arg("-"), Source::Stdin);
+        assert_eq!(
+            Source::from_arg("no/such/file"),
+            Source::File(PathBuf::from("no/such/file"))
+        );
+        let dir = env::temp_dir();
+        assert_eq!(
+            Source::from_arg(dir.to_str().unwrap()),
+            Source::Dir(dir.clone())
+        );
+    }
+
+    #[test]
+    fn test_load() {
+        // of its own, so that runs at the same time don't share it
+        let dir = env::temp_dir().join(format!("aoc_2024_input_test_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day_3"), "mul(2,4)\n").unwrap();
+
+        assert_eq!(
+            load(2024, 3, &Source::Dir(dir.clone())).unwrap(),
+            "mul(2,4)\n"
+        );
+        assert_eq!(
+            load(2024, 7, &Source::File(dir.join("day_3"))).unwrap(),
+            "mul(2,4)\n"
+        );
+
+        let err = load(2024, 4, &Source::Dir(dir.clone())).unwrap_err();
+        assert_eq!(err.day, 4);
+        assert_eq!(err.path, Some(dir.join("day_4")));
+        assert_eq!(err.error.kind(), io::ErrorKind::NotFound);
+        assert!(err.to_string().contains("day 4"));
+
+        let err = load(2023, 3, &Source::Dir(dir.clone())).unwrap_err();
+        assert_eq!(err.path, Some(dir.join("2023").join("day_3")));
+        assert!(err.to_string().contains("2023 day 3"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,46 @@
+//! Solutions to the Advent of Code 2024, and what is needed to run them:
+//! the `Solution` trait every day implements, a registry with all of them,
+//! and the runners used by the binary.
+
+pub mod alloc;
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod cancel;
+pub mod day_1;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_19;
+pub mod day_2;
+pub mod day_20;
+pub mod day_21;
+pub mod day_22;
+pub mod day_23;
+pub mod day_24;
+pub mod day_25;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+pub mod differential;
+pub mod fixtures;
+pub mod input;
+pub mod lint;
+pub mod parse;
+pub mod part;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod solution;
+pub mod utils;
+pub mod visual;
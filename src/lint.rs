@@ -0,0 +1,195 @@
+use crate::parse::{lines, Line, ParseError};
+
+/// Something a day expects of its input, which its parser may take for
+/// granted. Blocks are the groups of lines between blank lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    /// every line as long as the first one
+    Rectangular,
+    /// only these characters
+    Chars(&'static str),
+    /// exactly one of this character
+    One(char),
+    /// exactly this many blocks
+    Blocks(usize),
+    /// blocks of exactly this many lines
+    BlockLines(usize),
+    /// blocks of as many lines as the first one
+    EqualBlocks,
+    /// these, for the block with this index, from 0
+    Block(usize, &'static [Constraint]),
+    /// these, for each block on its own
+    EveryBlock(&'static [Constraint]),
+}
+
+/// Everything in `input` that breaks `constraints`, in the order of the
+/// lines. Unlike a parser, it doesn't stop at the first problem.
+pub fn check(constraints: &[Constraint], input: &str) -> Vec<ParseError> {
+    let mut ret = vec![];
+    check_lines(constraints, &lines(input), &mut ret);
+    ret.sort_by_key(|e| (e.line, e.column));
+    ret
+}
+
+fn check_lines(constraints: &[Constraint], lines: &[Line], errors: &mut Vec<ParseError>) {
+    for constraint in constraints {
+        match *constraint {
+            Constraint::Rectangular => rectangular(lines, errors),
+            Constraint::Chars(allowed) => {
+                errors.extend(lines.iter().filter_map(|l| l.only(allowed).err()));
+            }
+            Constraint::One(c) => one(lines, c, errors),
+            Constraint::Blocks(n) => {
+                let blocks = blocks(lines);
+                if blocks.len() < n {
+                    let what = format!("{n} blocks separated by blank lines");
+                    errors.push(ParseError::missing(lines, what));
+                } else if let Some(extra) = blocks.get(n) {
+                    errors.push(extra[0].error(format!("only {n} blocks")));
+                }
+            }
+            Constraint::BlockLines(n) => {
+                for block in blocks(lines) {
+                    block_lines(block, n, errors);
+                }
+            }
+            Constraint::EqualBlocks => {
+                let blocks = blocks(lines);
+                if let Some(first) = blocks.first() {
+                    for block in &blocks[1..] {
+                        block_lines(block, first.len(), errors);
+                    }
+                }
+            }
+            Constraint::Block(n, inner) => {
+                if let Some(block) = blocks(lines).get(n) {
+                    check_lines(inner, block, errors);
+                }
+            }
+            Constraint::EveryBlock(inner) => {
+                for block in blocks(lines) {
+                    check_lines(inner, block, errors);
+                }
+            }
+        }
+    }
+}
+
+/// `lines` split at blank lines, without them.
+fn blocks<'a, 'b>(lines: &'b [Line<'a>]) -> Vec<&'b [Line<'a>]> {
+    lines
+        .split(|l| l.text.trim().is_empty())
+        .filter(|b| !b.is_empty())
+        .collect()
+}
+
+fn rectangular(lines: &[Line], errors: &mut Vec<ParseError>) {
+    let Some(first) = lines.first() else {
+        return;
+    };
+    let width = first.text.chars().count();
+    errors.extend(
+        lines
+            .iter()
+            .filter(|l| l.text.chars().count() != width)
+            .map(|l| l.error(format!("a row of {width} characters"))),
+    );
+}
+
+fn one(lines: &[Line], c: char, errors: &mut Vec<ParseError>) {
+    let mut found = lines.iter().flat_map(|line| {
+        line.text
+            .match_indices(c)
+            .map(move |(n, s)| line.error_at(&line.text[n..n + s.len()], format!("a single `{c}`")))
+    });
+    if found.next().is_none() {
+        errors.push(ParseError::missing(lines, format!("a `{c}`")));
+    }
+    errors.extend(found);
+}
+
+fn block_lines(block: &[Line], n: usize, errors: &mut Vec<ParseError>) {
+    match block.get(n) {
+        Some(extra) => errors.push(extra.error(format!("a blank line after {n} lines"))),
+        None if block.len() < n => {
+            errors.push(ParseError::missing(block, format!("a block of {n} lines")));
+        }
+        None => (),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::{check, Constraint};
+
+    /// Where the problems are, and what was expected there.
+    fn problems(constraints: &[Constraint], input: &str) -> Vec<(usize, usize, String)> {
+        check(constraints, input)
+            .into_iter()
+            .map(|e| (e.line, e.column, e.expected))
+            .collect()
+    }
+
+    #[test]
+    fn test_grid() {
+        let constraints = [
+            Constraint::Rectangular,
+            Constraint::Chars("#.SE"),
+            Constraint::One('S'),
+            Constraint::One('E'),
+        ];
+        assert!(check(&constraints, "#S#\n#.E\n").is_empty());
+        assert_eq!(
+            problems(&constraints, "#S#\n#.\n#S.x\n"),
+            vec![
+                (2, 1, "a row of 3 characters".to_string()),
+                (3, 1, "a row of 3 characters".to_string()),
+                (3, 2, "a single `S`".to_string()),
+                (3, 4, "one of `#.SE`".to_string()),
+                (4, 1, "a `E`".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_blocks() {
+        let machines = [Constraint::BlockLines(3)];
+        assert!(check(&machines, "a\nb\nc\n\nd\ne\nf\n").is_empty());
+        assert_eq!(
+            problems(&machines, "a\nb\nc\nd\n\ne\nf\n\ng\nh\ni\n"),
+            vec![
+                (4, 1, "a blank line after 3 lines".to_string()),
+                (8, 1, "a block of 3 lines".to_string()),
+            ]
+        );
+
+        let schematics = [
+            Constraint::EqualBlocks,
+            Constraint::EveryBlock(&[Constraint::Rectangular]),
+        ];
+        assert_eq!(
+            problems(&schematics, "##\n..\n\n##\n.\n..\n"),
+            vec![
+                (5, 1, "a row of 2 characters".to_string()),
+                (6, 1, "a blank line after 2 lines".to_string()),
+            ]
+        );
+
+        let warehouse = [
+            Constraint::Blocks(2),
+            Constraint::Block(1, &[Constraint::Chars("<>")]),
+        ];
+        assert!(check(&warehouse, "#@#\n\n<>\n").is_empty());
+        assert_eq!(
+            problems(&warehouse, "#@#\n\n<v\n\n<\n"),
+            vec![
+                (3, 2, "one of `<>`".to_string()),
+                (5, 1, "only 2 blocks".to_string()),
+            ]
+        );
+        assert_eq!(
+            problems(&warehouse, "#@#\n"),
+            vec![(2, 1, "2 blocks separated by blank lines".to_string())]
+        );
+    }
+}
@@ -1,78 +1,549 @@
-mod day_1;
-mod day_10;
-mod day_11;
-mod day_12;
-mod day_13;
-mod day_14;
-mod day_15;
-mod day_16;
-mod day_17;
-mod day_18;
-mod day_19;
-mod day_2;
-mod day_20;
-mod day_21;
-mod day_22;
-mod day_23;
-mod day_24;
-mod day_25;
-mod day_3;
-mod day_4;
-mod day_5;
-mod day_6;
-mod day_7;
-mod day_8;
-mod day_9;
-pub mod utils;
+mod cli;
+mod scaffold;
 
-use std::env;
+use std::{env, io, path::Path, process::ExitCode, time::Duration};
 
-fn main() {
-    let mains = [
-        day_1::main,
-        day_2::main,
-        day_3::main,
-        day_4::main,
-        day_5::main,
-        day_6::main,
-        day_7::main,
-        day_8::main,
-        day_9::main,
-        day_10::main,
-        day_11::main,
-        day_12::main,
-        day_13::main,
-        day_14::main,
-        day_15::main,
-        day_16::main,
-        day_17::main,
-        day_18::main,
-        day_19::main,
-        day_20::main,
-        day_21::main,
-        day_22::main,
-        day_23::main,
-        day_24::main,
-        day_25::main,
-    ];
+use aoc_2024::{
+    alloc::{self, Counting},
+    answers::{Answers, AnswersError, Verdict},
+    bench::{self, Baseline},
+    input::{self, Source},
+    lint,
+    part::Part,
+    registry::{self, Registry},
+    report::{self, Format, Record, Status},
+    runner::{self, Job},
+    solution::{day_label, Params, DEFAULT_YEAR},
+    visual,
+};
+use cli::{Command, Days};
 
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        panic!(
-            "can't understand day to run. Add an argument from 1 to {} for the day.",
-            mains.len()
-        )
-    }
-    let day: usize = args[1].parse().unwrap_or_else(|_| {
-        panic!(
-            "can't understand day to run. Add an argument from 1 to {} for the day.",
-            mains.len()
-        )
-    });
-    match day {
-        i if i > mains.len() || i < 1 => {
-            panic!("No day {day}. Days range from 1 to {}.", mains.len())
-        }
-        i => mains[i - 1](),
+// only counts with --alloc-stats
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let registry = registry::all();
+    match cli::parse(&args, cli::DAYS_IN_EVENT) {
+        Ok(Command::Help) => {
+            println!("{}", cli::USAGE);
+            ExitCode::SUCCESS
+        }
+        Ok(Command::New { year, day }) => new(year, day),
+        Ok(Command::Lint { year, day, source }) => {
+            lint(&registry, year.unwrap_or(DEFAULT_YEAR), day, &source)
+        }
+        Ok(Command::List { year }) => {
+            list(&registry, year);
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Run {
+            year,
+            days,
+            part,
+            source,
+            format,
+            threads,
+            params,
+            alloc_stats,
+            timeout,
+            visualize,
+        }) => {
+            if alloc_stats {
+                alloc::enable();
+            }
+            if let Some(target) = visualize {
+                visual::enable(target);
+            }
+            run(
+                &registry,
+                &day_list(&registry, year, days),
+                part,
+                &source,
+                format,
+                threads,
+                &params,
+                timeout,
+            )
+        }
+        Ok(Command::Check {
+            year,
+            days,
+            source,
+            answers,
+            update,
+            format,
+            threads,
+            timeout,
+        }) => check(
+            &registry,
+            &day_list(&registry, year, days),
+            &source,
+            &answers,
+            update,
+            format,
+            threads,
+            timeout,
+        ),
+        Ok(Command::Bench {
+            year,
+            day,
+            part,
+            source,
+            iterations,
+            save,
+            compare,
+            params,
+        }) => match job(&registry, year.unwrap_or(DEFAULT_YEAR), day, part, &params) {
+            Ok(job) => run_bench(
+                &registry,
+                &job,
+                &source,
+                iterations,
+                save.as_deref(),
+                compare.as_deref(),
+            ),
+            Err(e) => {
+                eprintln!("error: {e}");
+                ExitCode::FAILURE
+            }
+        },
+        Err(e) => {
+            eprintln!("error: {e}\n\n{}", cli::USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Years and days to solve. Days given by number are of `year`, or of the
+/// default year; all days are those of `year`, or of every year.
+fn day_list(registry: &Registry, year: Option<usize>, days: Days) -> Vec<(usize, usize)> {
+    let of_year = |days: Vec<usize>| {
+        let year = year.unwrap_or(DEFAULT_YEAR);
+        days.into_iter().map(|day| (year, day)).collect()
+    };
+    match days {
+        Days::One(day) => of_year(vec![day]),
+        Days::Range(first, last) => of_year((first..=last).collect()),
+        Days::All => registry
+            .iter()
+            .filter(|s| year.is_none_or(|year| year == s.year()))
+            .map(|s| (s.year(), s.day()))
+            .collect(),
+    }
+}
+
+/// The `part` of `day`, or both if `None`, with `params` over its defaults.
+fn job(
+    registry: &Registry,
+    year: usize,
+    day: usize,
+    part: Option<Part>,
+    params: &[(String, usize)],
+) -> Result<Job, String> {
+    let label = day_label(year, day);
+    let Some(solver) = registry.get(year, day) else {
+        return Err(format!("{label} is not solved yet"));
+    };
+    let parts: Vec<Part> = solver
+        .parts()
+        .iter()
+        .filter(|&&p| part.is_none_or(|part| part == p))
+        .copied()
+        .collect();
+    if parts.is_empty() {
+        return Err(format!("{label} has no part {}", part.unwrap()));
+    }
+    let params = Params::new(solver.params(), params).map_err(|e| format!("{label}: {e}"))?;
+    Ok(Job {
+        year,
+        day,
+        parts,
+        params,
+        timeout: None,
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run(
+    registry: &Registry,
+    days: &[(usize, usize)],
+    part: Option<Part>,
+    source: &Source,
+    format: Format,
+    threads: usize,
+    params: &[(String, usize)],
+    timeout: Option<Duration>,
+) -> ExitCode {
+    let text = format == Format::Text;
+    let mut failed = false;
+    let mut jobs: Vec<Job> = vec![];
+    for &(year, day) in days {
+        match job(registry, year, day, part, params) {
+            Ok(job) => jobs.push(Job { timeout, ..job }),
+            // only worth complaining about when this day was asked for alone
+            Err(e) if days.len() == 1 => {
+                eprintln!("error: {e}");
+                failed = true;
+            }
+            Err(_) => {}
+        }
+    }
+    let mut runs: Vec<runner::DayRun> = vec![];
+    let mut records: Vec<Record> = vec![];
+    let results = runner::run_days(registry, &jobs, source, threads);
+    for (
+        Job {
+            year, day, parts, ..
+        },
+        result,
+    ) in jobs.iter().zip(results)
+    {
+        if text && days.len() > 1 {
+            let title = registry.get(*year, *day).unwrap().title();
+            println!("{}: {title}", day_label(*year, *day));
+        }
+        match result {
+            Ok(run) => {
+                for p in run.parts.iter() {
+                    if p.timed_out {
+                        failed = true;
+                    }
+                    if text {
+                        println!("part {}: {}", p.part, shown_answer(p));
+                    }
+                    records.push(Record::solved(*year, *day, p, Status::Ok));
+                }
+                runs.push(run);
+            }
+            Err(e) => {
+                eprintln!("error: {e}");
+                failed = true;
+                records.extend(
+                    parts
+                        .iter()
+                        .map(|&p| Record::failed(*year, *day, p, e.to_string())),
+                );
+            }
+        }
+    }
+    match format {
+        Format::Text => {
+            if days.len() > 1 {
+                print!("\n{}", runner::timing_table(&runs));
+            }
+            if alloc::enabled() {
+                print!("\n{}", runner::alloc_table(&runs));
+            }
+        }
+        Format::Json => print!("{}", report::json(&records)),
+        Format::Csv => print!("{}", report::csv(&records)),
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// The answer of `p`, unless it ran out of time.
+fn shown_answer(p: &runner::PartRun) -> String {
+    if p.timed_out {
+        format!("timed out after {}", runner::format_duration(p.duration))
+    } else {
+        p.answer.to_string()
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn check(
+    registry: &Registry,
+    days: &[(usize, usize)],
+    source: &Source,
+    path: &Path,
+    update: bool,
+    format: Format,
+    threads: usize,
+    timeout: Option<Duration>,
+) -> ExitCode {
+    let text = format == Format::Text;
+    let mut answers = match Answers::load(path) {
+        Ok(answers) => answers,
+        Err(AnswersError::Io(e)) if update && e.kind() == io::ErrorKind::NotFound => {
+            Answers::default()
+        }
+        Err(e) => {
+            eprintln!("error: can't read answers from {}: {e}", path.display());
+            if matches!(e, AnswersError::Io(_)) {
+                eprintln!("Record them with `check --update`.");
+            }
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut records: Vec<Record> = vec![];
+    let mut jobs: Vec<Job> = vec![];
+    for &(year, day) in days {
+        match registry.get(year, day) {
+            Some(solver) => jobs.push(Job {
+                timeout,
+                ..Job::all_parts(solver)
+            }),
+            None if days.len() == 1 => {
+                eprintln!("error: {} is not solved yet", day_label(year, day));
+                return ExitCode::FAILURE;
+            }
+            None => {}
+        }
+    }
+    let mut recorded = 0;
+    let results = runner::run_days(registry, &jobs, source, threads);
+    for (
+        Job {
+            year, day, parts, ..
+        },
+        result,
+    ) in jobs.iter().zip(results)
+    {
+        let (year, day) = (*year, *day);
+        let run = match result {
+            Ok(run) => run,
+            Err(e) => {
+                eprintln!("error: {e}");
+                records.extend(
+                    parts
+                        .iter()
+                        .map(|&p| Record::failed(year, day, p, e.to_string())),
+                );
+                continue;
+            }
+        };
+        for p in run.parts.iter() {
+            let what = format!("{} part {}", day_label(year, day), p.part);
+            if p.timed_out {
+                if text {
+                    println!("{what}: {}", shown_answer(p));
+                }
+                records.push(Record::solved(year, day, p, Status::Timeout));
+                continue;
+            }
+            let verdict = answers.check(year, day, p.part, &p.answer);
+            let changed = verdict != Verdict::Ok;
+            let record = match verdict {
+                Verdict::Ok => {
+                    if text {
+                        println!("{what}: ok");
+                    }
+                    Record::solved(year, day, p, Status::Ok)
+                }
+                Verdict::Missing => {
+                    if text {
+                        println!("{what}: not recorded, got {}", p.answer);
+                    }
+                    Record::solved(year, day, p, Status::Missing)
+                }
+                Verdict::Mismatch { expected } => {
+                    if text {
+                        println!("{what}: MISMATCH");
+                        println!("  - expected: {expected}");
+                        println!("  + got:      {}", p.answer);
+                    }
+                    Record {
+                        expected: Some(expected),
+                        ..Record::solved(year, day, p, Status::Mismatch)
+                    }
+                }
+            };
+            records.push(record);
+            if update && changed {
+                answers.record(year, day, p.part, &p.answer);
+                recorded += 1;
+            }
+        }
+    }
+    let count = |status: Status| records.iter().filter(|r| r.status == status).count();
+    let (mismatched, failed) = (count(Status::Mismatch), count(Status::Error));
+    let timed_out = count(Status::Timeout);
+    match format {
+        Format::Text => println!(
+            "\n{} ok, {mismatched} mismatched, {} not recorded, {failed} failed to run{}",
+            count(Status::Ok),
+            count(Status::Missing),
+            if timed_out > 0 {
+                format!(", {timed_out} timed out")
+            } else {
+                String::new()
+            }
+        ),
+        Format::Json => print!("{}", report::json(&records)),
+        Format::Csv => print!("{}", report::csv(&records)),
+    }
+    if recorded > 0 {
+        match answers.save(path) {
+            Ok(()) if text => println!("recorded {recorded} answers in {}", path.display()),
+            Ok(()) => {}
+            Err(e) => {
+                eprintln!("error: can't write answers to {}: {e}", path.display());
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    if failed > 0 || timed_out > 0 || (mismatched > 0 && !update) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn run_bench(
+    registry: &Registry,
+    job: &Job,
+    source: &Source,
+    iterations: usize,
+    save: Option<&Path>,
+    compare: Option<&Path>,
+) -> ExitCode {
+    let (year, day) = (job.year, job.day);
+    let solver = registry
+        .get(year, day)
+        .expect("days come from the registry");
+    let input = match input::load(year, day, source) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let baseline = match compare.map(Baseline::load).transpose() {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            let path = compare.unwrap().display();
+            eprintln!("error: can't read benchmark results from {path}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    println!(
+        "{}: {}, {iterations} iterations",
+        day_label(year, day),
+        solver.title()
+    );
+    let results = match bench::bench(solver, &input, &job.parts, &job.params, iterations) {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    print!("{}", bench::table(year, day, &results, baseline.as_ref()));
+    if let Some(path) = save {
+        // keep what other days saved there
+        let mut saved = match Baseline::load(path) {
+            Ok(saved) => saved,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Baseline::default(),
+            Err(e) => {
+                eprintln!("error: can't read {}: {e}", path.display());
+                return ExitCode::FAILURE;
+            }
+        };
+        for (phase, stats) in results {
+            saved.insert(year, day, phase, stats);
+        }
+        if let Err(e) = saved.save(path) {
+            eprintln!("error: can't write {}: {e}", path.display());
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn new(year: usize, day: usize) -> ExitCode {
+    let root = match env::current_dir() {
+        Ok(dir) if dir.join("Cargo.toml").is_file() => dir,
+        _ => {
+            eprintln!("error: run `new` from the crate root, where Cargo.toml is");
+            return ExitCode::FAILURE;
+        }
     };
+    match scaffold::new_day(&root, year, day) {
+        Ok(written) => {
+            for path in written {
+                let path = path.strip_prefix(&root).unwrap_or(&path);
+                println!("wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Shows what the input of `day` breaks of its constraints or, if nothing,
+/// whether it parses.
+fn lint(registry: &Registry, year: usize, day: usize, source: &Source) -> ExitCode {
+    let label = day_label(year, day);
+    let Some(solver) = registry.get(year, day) else {
+        eprintln!("error: {label} is not solved yet");
+        return ExitCode::FAILURE;
+    };
+    let input = match input::load(year, day, source) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let problems = lint::check(solver.constraints(), &input);
+    for problem in &problems {
+        println!("{label}, {problem}");
+    }
+    if !problems.is_empty() {
+        println!("{} problems", problems.len());
+        return ExitCode::FAILURE;
+    }
+    if solver.constraints().is_empty() {
+        println!("{label} declares no constraints");
+    }
+    match solver.parse(&input) {
+        Ok(_) => {
+            println!("{label}: ok");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// The days of `year`, or of every year under a line with the year.
+fn list(registry: &Registry, year: Option<usize>) {
+    let years = match year {
+        Some(year) => vec![year],
+        None => registry.years(),
+    };
+    for (n, &year) in years.iter().enumerate() {
+        if years.len() > 1 {
+            if n > 0 {
+                println!();
+            }
+            println!("{year}");
+        }
+        list_year(registry, year);
+    }
+}
+
+fn list_year(registry: &Registry, year: usize) {
+    for solver in registry.iter().filter(|s| s.year() == year) {
+        let day = solver.day();
+        let available = match input::load(year, day, &Source::Default) {
+            Ok(_) => "input available",
+            Err(_) => "no input",
+        };
+        println!("day {day:>2}  {:<24}  {available}", solver.title());
+        for param in solver.params() {
+            println!("        {:<16}  {}", param.to_string(), param.help);
+        }
+    }
 }
@@ -0,0 +1,278 @@
+use std::{fmt, str::FromStr};
+
+use regex::{Captures, Regex};
+
+use crate::solution::day_label;
+
+/// Where an input stops making sense, and what was expected there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// year and day, `None` until the error leaves the day that found it
+    pub day: Option<(usize, usize)>,
+    /// counted from 1
+    pub line: usize,
+    /// counted from 1, in characters
+    pub column: usize,
+    /// what was found, empty if nothing was
+    pub text: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, expected: impl Into<String>) -> Self {
+        ParseError {
+            day: None,
+            line,
+            column,
+            text: text.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Something missing after the last of `lines`.
+    pub fn missing(lines: &[Line], expected: impl Into<String>) -> Self {
+        let line = lines.last().map_or(1, |l| l.number + 1);
+        Self::new(line, 1, "", expected)
+    }
+
+    pub fn in_day(self, year: usize, day: usize) -> Self {
+        ParseError {
+            day: Some((year, day)),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((year, day)) = self.day {
+            write!(f, "{}, ", day_label(year, day))?;
+        }
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+        if self.text.is_empty() {
+            write!(f, ", found nothing")
+        } else {
+            write!(f, ", found `{}`", self.text)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A line of an input, with its number to point at it in errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    /// counted from 1
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// The whole line is wrong.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        ParseError::new(self.number, 1, self.text, expected)
+    }
+
+    /// `at`, which must be a slice of this line, is wrong. Anything else is
+    /// taken as missing from the end of the line.
+    pub fn error_at(&self, at: &str, expected: impl Into<String>) -> ParseError {
+        let offset = (at.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+        match self.text.get(..offset) {
+            Some(before) if offset + at.len() <= self.text.len() => {
+                ParseError::new(self.number, before.chars().count() + 1, at, expected)
+            }
+            _ => ParseError::new(self.number, self.text.chars().count() + 1, "", expected),
+        }
+    }
+
+    /// `s`, a slice of this line, as a number.
+    pub fn number<T: FromStr>(&self, s: &str) -> Result<T, ParseError> {
+        s.parse().map_err(|_| self.error_at(s, "a number"))
+    }
+
+    /// The numbers between `sep`s.
+    pub fn numbers<T: FromStr>(&self, sep: &str) -> Result<Vec<T>, ParseError> {
+        self.text.split(sep).map(|n| self.number(n)).collect()
+    }
+
+    pub fn split_once(&self, sep: &str) -> Result<(&'a str, &'a str), ParseError> {
+        self.text
+            .split_once(sep)
+            .ok_or_else(|| self.error(format!("`{sep}`")))
+    }
+
+    /// `re` must match, otherwise the line is not `expected`.
+    pub fn captures(&self, re: &Regex, expected: &str) -> Result<Captures<'a>, ParseError> {
+        re.captures(self.text).ok_or_else(|| self.error(expected))
+    }
+
+    /// Fails at the first character not in `allowed`.
+    pub fn only(&self, allowed: &str) -> Result<(), ParseError> {
+        match self
+            .text
+            .char_indices()
+            .find(|&(_, c)| !allowed.contains(c))
+        {
+            Some((n, c)) => Err(self.error_at(
+                &self.text[n..n + c.len_utf8()],
+                format!("one of `{allowed}`"),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The lines of `input` without the blank ones at the end. Windows line
+/// endings are fine.
+pub fn lines(input: &str) -> Vec<Line<'_>> {
+    input
+        .trim_end()
+        .lines()
+        .enumerate()
+        .map(|(n, text)| Line {
+            number: n + 1,
+            text,
+        })
+        .collect()
+}
+
+/// The only line of `input`.
+pub fn single_line(input: &str) -> Result<Line<'_>, ParseError> {
+    match lines(input)[..] {
+        [line] => Ok(line),
+        [] => Err(ParseError::missing(&[], "a line")),
+        [_, extra, ..] => Err(extra.error("nothing after the first line")),
+    }
+}
+
+/// The lines of `input` in groups separated by blank lines.
+pub fn sections(input: &str) -> Vec<Vec<Line<'_>>> {
+    let mut ret: Vec<Vec<Line>> = vec![vec![]];
+    for line in lines(input) {
+        if line.text.trim().is_empty() {
+            ret.push(vec![]);
+        } else {
+            ret.last_mut().unwrap().push(line);
+        }
+    }
+    ret
+}
+
+/// `lines` as rows of a rectangle made of characters in `allowed`.
+pub fn grid(lines: &[Line], allowed: &str) -> Result<Vec<String>, ParseError> {
+    let Some(first) = lines.first() else {
+        return Err(ParseError::missing(lines, "a grid"));
+    };
+    let width = first.text.chars().count();
+    for line in lines {
+        line.only(allowed)?;
+        if line.text.chars().count() != width {
+            return Err(line.error(format!("a row of {width} characters")));
+        }
+    }
+    Ok(lines.iter().map(|l| l.text.to_string()).collect())
+}
+
+/// Row and column, from 0, of the only `c` in `lines`, which is `what`.
+pub fn only_one(lines: &[Line], c: char, what: &str) -> Result<(usize, usize), ParseError> {
+    let mut found = lines.iter().enumerate().flat_map(|(i, line)| {
+        line.text
+            .char_indices()
+            .filter(move |&(_, d)| d == c)
+            .map(move |(n, _)| (i, n))
+    });
+    let Some(first) = found.next() else {
+        return Err(ParseError::missing(lines, what));
+    };
+    if let Some((i, n)) = found.next() {
+        let line = lines[i];
+        return Err(line.error_at(&line.text[n..n + c.len_utf8()], format!("a single `{c}`")));
+    }
+    let (i, n) = first;
+    Ok((i, lines[i].text[..n].chars().count()))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use regex::Regex;
+
+    use super::{grid, lines, only_one, sections, single_line, Line, ParseError};
+
+    #[test]
+    fn test_lines() {
+        let ls = lines("a\r\nb\n\nc\n\n");
+        let texts: Vec<&str> = ls.iter().map(|l| l.text).collect();
+        assert_eq!(texts, vec!["a", "b", "", "c"]);
+        assert_eq!(ls[3].number, 4);
+        assert!(lines("").is_empty());
+        assert_eq!(single_line("a\n").unwrap().text, "a");
+        assert_eq!(single_line("a\nb").unwrap_err().line, 2);
+        assert_eq!(single_line(" \n").unwrap_err().line, 1);
+
+        let ss = sections("1\n2\n\n3\n");
+        assert_eq!(ss.len(), 2);
+        assert_eq!(
+            ss[1][0],
+            Line {
+                number: 4,
+                text: "3"
+            }
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let line = Line {
+            number: 3,
+            text: "päx 12 1b",
+        };
+        assert_eq!(line.number::<u8>(&line.text[5..7]), Ok(12));
+        let e = line.numbers::<u8>(" ").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (3, 1, "päx"));
+        let e = line.number::<u8>(&line.text[8..]).unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (8, "1b"));
+        assert_eq!(
+            e.clone().in_day(2024, 5).to_string(),
+            "day 5, line 3, column 8: expected a number, found `1b`"
+        );
+        assert_eq!(
+            e.in_day(2023, 5).to_string(),
+            "2023 day 5, line 3, column 8: expected a number, found `1b`"
+        );
+        let e = line.error_at("elsewhere", "more");
+        assert_eq!(
+            e.to_string(),
+            "line 3, column 10: expected more, found nothing"
+        );
+        let e = line.only("päx ").unwrap_err();
+        assert_eq!((e.column, e.text.as_str()), (5, "1"));
+        assert_eq!(line.split_once(",").unwrap_err().expected, "`,`");
+        let re = Regex::new("^\\d+$").unwrap();
+        assert_eq!(line.captures(&re, "digits").unwrap_err().column, 1);
+    }
+
+    #[test]
+    fn test_grid() {
+        assert_eq!(grid(&lines("#.\r\n.#\n"), "#.").unwrap(), vec!["#.", ".#"]);
+        let e = grid(&lines("#.\n.\n"), "#.").unwrap_err();
+        assert_eq!((e.line, e.text.as_str()), (2, "."));
+        let e = grid(&lines("#.\n\n.#"), "#.").unwrap_err();
+        assert_eq!(e.line, 2);
+        let e = grid(&lines("#.\n.o"), "#.").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 2, "o"));
+        assert_eq!(grid(&[], "#"), Err(ParseError::new(1, 1, "", "a grid")));
+
+        assert_eq!(only_one(&lines("#.\n.S"), 'S', "a start"), Ok((1, 1)));
+        let e = only_one(&lines("#.\n.."), 'S', "a start").unwrap_err();
+        assert_eq!((e.line, e.expected.as_str()), (3, "a start"));
+        let e = only_one(&lines("S.\n.S"), 'S', "a start").unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_str()),
+            (2, 2, "a single `S`")
+        );
+    }
+}
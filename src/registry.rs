@@ -0,0 +1,180 @@
+use crate::{
+    day_1, day_10, day_11, day_12, day_13, day_14, day_15, day_16, day_17, day_18, day_19, day_2,
+    day_20, day_21, day_22, day_23, day_24, day_25, day_3, day_4, day_5, day_6, day_7, day_8,
+    day_9,
+    solution::{day_label, Solver},
+};
+
+/// Every day we know how to solve, kept in order of year and day.
+pub struct Registry {
+    solvers: Vec<&'static dyn Solver>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self { solvers: vec![] }
+    }
+
+    pub fn register(&mut self, solver: &'static dyn Solver) {
+        match self
+            .solvers
+            .binary_search_by_key(&(solver.year(), solver.day()), |s| (s.year(), s.day()))
+        {
+            Ok(_) => panic!(
+                "{} registered twice",
+                day_label(solver.year(), solver.day())
+            ),
+            Err(pos) => self.solvers.insert(pos, solver),
+        }
+    }
+
+    pub fn get(&self, year: usize, day: usize) -> Option<&'static dyn Solver> {
+        self.iter().find(|s| (s.year(), s.day()) == (year, day))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'static dyn Solver> + use<'_> {
+        self.solvers.iter().copied()
+    }
+
+    /// The days of `year`.
+    pub fn days(&self, year: usize) -> Vec<usize> {
+        self.iter()
+            .filter(|s| s.year() == year)
+            .map(|s| s.day())
+            .collect()
+    }
+
+    pub fn years(&self) -> Vec<usize> {
+        let mut ret: Vec<usize> = self.iter().map(|s| s.year()).collect();
+        ret.dedup();
+        ret
+    }
+
+    pub fn len(&self) -> usize {
+        self.solvers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.solvers.is_empty()
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The registry with all the days of every year.
+pub fn all() -> Registry {
+    let mut registry = Registry::new();
+    registry.register(&day_1::Day1);
+    registry.register(&day_2::Day2);
+    registry.register(&day_3::Day3);
+    registry.register(&day_4::Day4);
+    registry.register(&day_5::Day5);
+    registry.register(&day_6::Day6);
+    registry.register(&day_7::Day7);
+    registry.register(&day_8::Day8);
+    registry.register(&day_9::Day9);
+    registry.register(&day_10::Day10);
+    registry.register(&day_11::Day11);
+    registry.register(&day_12::Day12);
+    registry.register(&day_13::Day13);
+    registry.register(&day_14::Day14);
+    registry.register(&day_15::Day15);
+    registry.register(&day_16::Day16);
+    registry.register(&day_17::Day17);
+    registry.register(&day_18::Day18);
+    registry.register(&day_19::Day19);
+    registry.register(&day_20::Day20);
+    registry.register(&day_21::Day21);
+    registry.register(&day_22::Day22);
+    registry.register(&day_23::Day23);
+    registry.register(&day_24::Day24);
+    registry.register(&day_25::Day25);
+    registry
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::{all, Registry};
+    use crate::{
+        answer::Answer,
+        day_3::Day3,
+        day_7::Day7,
+        parse::ParseError,
+        part::Part,
+        solution::{Params, Solution},
+    };
+
+    struct NotQuiteLisp;
+
+    impl Solution for NotQuiteLisp {
+        type Input = i64;
+        const YEAR: usize = 2015;
+        const DAY: usize = 1;
+        const TITLE: &'static str = "Not Quite Lisp";
+        const PARTS: &'static [Part] = &[Part::One];
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            Ok(input.chars().map(|c| if c == '(' { 1 } else { -1 }).sum())
+        }
+
+        fn part1(input: &Self::Input, _params: &Params) -> Answer {
+            (*input).into()
+        }
+    }
+
+    #[test]
+    fn test_all() {
+        let registry = all();
+        assert_eq!(registry.len(), 25);
+        assert_eq!(registry.years(), vec![2024]);
+        assert_eq!(registry.days(2024), (1..=25).collect::<Vec<usize>>());
+        assert_eq!(registry.get(2024, 16).unwrap().title(), "Reindeer Maze");
+        assert!(registry.get(2024, 26).is_none());
+        assert!(registry.get(2023, 16).is_none());
+        assert_eq!(registry.get(2024, 25).unwrap().parts(), &[Part::One]);
+    }
+
+    #[test]
+    fn test_answers() {
+        let registry = all();
+        let day_11 = registry.get(2024, 11).unwrap();
+        let parsed = day_11.parse("125 17\n").unwrap();
+        assert_eq!(
+            day_11.solve(&parsed, Part::One, &Params::defaults(day_11.params())),
+            Answer::Int(55312)
+        );
+        let day_17 = registry.get(2024, 17).unwrap();
+        let parsed = day_17
+            .parse("Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0\n")
+            .unwrap();
+        assert_eq!(
+            day_17.solve(&parsed, Part::One, &Params::default()),
+            Answer::from("4,6,3,5,6,3,5,2,1,0")
+        );
+    }
+
+    #[test]
+    fn test_register() {
+        let mut registry = Registry::new();
+        assert!(registry.is_empty());
+        registry.register(&Day7);
+        registry.register(&Day3);
+        registry.register(&NotQuiteLisp);
+        assert_eq!(registry.days(2024), vec![3, 7]);
+        assert_eq!(registry.years(), vec![2015, 2024]);
+        assert_eq!(registry.iter().next().unwrap().title(), "Not Quite Lisp");
+        assert!(registry.get(2015, 3).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_register_twice() {
+        let mut registry = Registry::new();
+        registry.register(&Day3);
+        registry.register(&Day3);
+    }
+}
@@ -0,0 +1,299 @@
+use std::{fmt, fmt::Write, str::FromStr, time::Duration};
+
+use crate::{alloc::AllocStats, answer::Answer, part::Part, runner::PartRun};
+
+/// How results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// for people, as the results come
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("no format {s}. Formats are text, json and csv.")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    /// solved, but not what was recorded
+    Mismatch,
+    /// solved, with nothing recorded to compare with
+    Missing,
+    /// could not be solved
+    Error,
+    /// gave up after the time allowed
+    Timeout,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Status::Ok => "ok",
+            Status::Mismatch => "mismatch",
+            Status::Missing => "missing",
+            Status::Error => "error",
+            Status::Timeout => "timeout",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// The outcome of one part of one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub year: usize,
+    pub day: usize,
+    pub part: Part,
+    pub answer: Option<Answer>,
+    pub duration: Option<Duration>,
+    pub status: Status,
+    /// the recorded answer, when it was not the one we got
+    pub expected: Option<String>,
+    pub error: Option<String>,
+    /// only when counting allocations
+    pub alloc: Option<AllocStats>,
+}
+
+impl Record {
+    /// A timed out `run` has no answer, and that is its status.
+    pub fn solved(year: usize, day: usize, run: &PartRun, status: Status) -> Self {
+        Record {
+            year,
+            day,
+            part: run.part,
+            answer: (!run.timed_out).then(|| run.answer.clone()),
+            duration: Some(run.duration),
+            status: if run.timed_out {
+                Status::Timeout
+            } else {
+                status
+            },
+            expected: None,
+            error: None,
+            alloc: run.alloc,
+        }
+    }
+
+    pub fn failed(year: usize, day: usize, part: Part, error: String) -> Self {
+        Record {
+            year,
+            day,
+            part,
+            answer: None,
+            duration: None,
+            status: Status::Error,
+            expected: None,
+            error: Some(error),
+            alloc: None,
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut ret = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            c if c.is_control() => write!(ret, "\\u{:04x}", c as u32).unwrap(),
+            c => ret.push(c),
+        }
+    }
+    ret.push('"');
+    ret
+}
+
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Int(n) => n.to_string(),
+        // too big for many JSON readers to keep exact
+        Answer::BigInt(n) => json_string(&n.to_string()),
+        Answer::Text(_) | Answer::Coord(..) => json_string(&answer.to_string()),
+    }
+}
+
+/// A JSON array with one object per record.
+pub fn json(records: &[Record]) -> String {
+    let or_null = |s: Option<String>| s.unwrap_or("null".to_string());
+    let mut ret = String::from("[");
+    for (n, r) in records.iter().enumerate() {
+        let sep = if n == 0 { "" } else { "," };
+        write!(
+            ret,
+            "{sep}\n  {{\"year\": {}, \"day\": {}, \"part\": {}, \"answer\": {}, \
+             \"answer_type\": {}, \"duration_ns\": {}, \"status\": \"{}\", \"expected\": {}, \
+             \"error\": {}, \"allocations\": {}, \"allocated_bytes\": {}, \"peak_bytes\": {}}}",
+            r.year,
+            r.day,
+            r.part,
+            or_null(r.answer.as_ref().map(json_answer)),
+            or_null(r.answer.as_ref().map(|a| json_string(a.kind()))),
+            or_null(r.duration.map(|d| d.as_nanos().to_string())),
+            r.status,
+            or_null(r.expected.as_deref().map(json_string)),
+            or_null(r.error.as_deref().map(json_string)),
+            or_null(r.alloc.map(|a| a.allocations.to_string())),
+            or_null(r.alloc.map(|a| a.bytes.to_string())),
+            or_null(r.alloc.map(|a| a.peak.to_string())),
+        )
+        .unwrap();
+    }
+    ret.push_str(if records.is_empty() { "]\n" } else { "\n]\n" });
+    ret
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// A header and one line per record. Missing values are left empty.
+pub fn csv(records: &[Record]) -> String {
+    let mut ret = String::from(
+        "year,day,part,answer,answer_type,duration_ns,status,expected,error,allocations,\
+         allocated_bytes,peak_bytes\n",
+    );
+    for r in records {
+        let fields = [
+            r.year.to_string(),
+            r.day.to_string(),
+            r.part.to_string(),
+            r.answer.as_ref().map(|a| a.to_string()).unwrap_or_default(),
+            r.answer
+                .as_ref()
+                .map(|a| a.kind().to_string())
+                .unwrap_or_default(),
+            r.duration
+                .map(|d| d.as_nanos().to_string())
+                .unwrap_or_default(),
+            r.status.to_string(),
+            r.expected.clone().unwrap_or_default(),
+            r.error.clone().unwrap_or_default(),
+            r.alloc
+                .map(|a| a.allocations.to_string())
+                .unwrap_or_default(),
+            r.alloc.map(|a| a.bytes.to_string()).unwrap_or_default(),
+            r.alloc.map(|a| a.peak.to_string()).unwrap_or_default(),
+        ];
+        let fields: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        writeln!(ret, "{}", fields.join(",")).unwrap();
+    }
+    ret
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::time::Duration;
+
+    use super::{csv, json, Format, Record, Status};
+    use crate::{alloc::AllocStats, answer::Answer, part::Part, runner::PartRun};
+
+    fn records() -> Vec<Record> {
+        let run = PartRun {
+            part: Part::One,
+            answer: Answer::from("4,6,3"),
+            duration: Duration::from_micros(12),
+            alloc: None,
+            timed_out: false,
+        };
+        let mut mismatch = Record::solved(
+            2024,
+            18,
+            &PartRun {
+                part: Part::Two,
+                answer: Answer::Coord(6, 1),
+                duration: Duration::from_nanos(7),
+                alloc: Some(AllocStats {
+                    allocations: 2,
+                    bytes: 48,
+                    peak: 32,
+                }),
+                timed_out: false,
+            },
+            Status::Mismatch,
+        );
+        mismatch.expected = Some("6,2".to_string());
+        vec![
+            Record::solved(2024, 17, &run, Status::Ok),
+            mismatch,
+            Record::failed(2023, 19, Part::One, "no \"input\"".to_string()),
+            Record::solved(
+                2024,
+                20,
+                &PartRun {
+                    timed_out: true,
+                    ..run
+                },
+                Status::Ok,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert_eq!("csv".parse(), Ok(Format::Csv));
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(json(&[]), "[]\n");
+        let text = json(&records());
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(
+            lines[1],
+            "  {\"year\": 2024, \"day\": 17, \"part\": 1, \"answer\": \"4,6,3\", \
+             \"answer_type\": \"text\", \"duration_ns\": 12000, \"status\": \"ok\", \
+             \"expected\": null, \"error\": null, \"allocations\": null, \
+             \"allocated_bytes\": null, \"peak_bytes\": null},"
+        );
+        assert!(lines[2].contains("\"answer_type\": \"coord\""));
+        assert!(lines[2].contains("\"expected\": \"6,2\""));
+        assert!(
+            lines[2].ends_with("\"allocations\": 2, \"allocated_bytes\": 48, \"peak_bytes\": 32},")
+        );
+        assert_eq!(
+            lines[3],
+            "  {\"year\": 2023, \"day\": 19, \"part\": 1, \"answer\": null, \
+             \"answer_type\": null, \"duration_ns\": null, \"status\": \"error\", \
+             \"expected\": null, \"error\": \"no \\\"input\\\"\", \"allocations\": null, \
+             \"allocated_bytes\": null, \"peak_bytes\": null},"
+        );
+        assert!(lines[4].contains(
+            "\"answer\": null, \"answer_type\": null, \"duration_ns\": 12000, \
+             \"status\": \"timeout\""
+        ));
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            csv(&records()),
+            "year,day,part,answer,answer_type,duration_ns,status,expected,error,allocations,\
+             allocated_bytes,peak_bytes\n\
+             2024,17,1,\"4,6,3\",text,12000,ok,,,,,\n\
+             2024,18,2,\"6,1\",coord,7,mismatch,\"6,2\",,2,48,32\n\
+             2023,19,1,,,,error,,\"no \"\"input\"\"\",,,\n\
+             2024,20,1,,,12000,timeout,,,,,\n"
+        );
+    }
+}
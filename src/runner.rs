@@ -0,0 +1,573 @@
+use std::{
+    fmt::{self, Write},
+    panic,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    alloc::{self, format_bytes, AllocStats},
+    answer::Answer,
+    cancel::Cancel,
+    input::{self, InputError, Source},
+    parse::ParseError,
+    part::Part,
+    registry::Registry,
+    solution::{day_label, short_day_label, Params, Solver},
+    visual,
+};
+
+/// One part of a day, solved.
+pub struct PartRun {
+    pub part: Part,
+    pub answer: Answer,
+    pub duration: Duration,
+    /// only when counting allocations
+    pub alloc: Option<AllocStats>,
+    /// ran out of time, so the answer means nothing
+    pub timed_out: bool,
+}
+
+/// One day, parsed and solved, with the time each step took.
+pub struct DayRun {
+    pub year: usize,
+    pub day: usize,
+    pub title: &'static str,
+    pub parse: Duration,
+    pub parse_alloc: Option<AllocStats>,
+    pub parts: Vec<PartRun>,
+}
+
+impl DayRun {
+    pub fn part(&self, part: Part) -> Option<&PartRun> {
+        self.parts.iter().find(|p| p.part == part)
+    }
+
+    pub fn total(&self) -> Duration {
+        self.parse + self.parts.iter().map(|p| p.duration).sum::<Duration>()
+    }
+}
+
+/// What to solve of one day.
+pub struct Job {
+    pub year: usize,
+    pub day: usize,
+    pub parts: Vec<Part>,
+    pub params: Params,
+    /// for each part
+    pub timeout: Option<Duration>,
+}
+
+impl Job {
+    /// Every part of `solver`, with its default parameters.
+    pub fn all_parts(solver: &dyn Solver) -> Self {
+        Job {
+            year: solver.year(),
+            day: solver.day(),
+            parts: solver.parts().to_vec(),
+            params: Params::defaults(solver.params()),
+            timeout: None,
+        }
+    }
+}
+
+/// Why a day could not be solved.
+#[derive(Debug)]
+pub enum RunError {
+    Input(InputError),
+    Parse(ParseError),
+    /// the parameters don't suit the input
+    Params(String),
+    /// the solver has a bug
+    Panic(String),
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::Input(e) => write!(f, "{e}"),
+            RunError::Parse(e) => write!(f, "can't parse input for {e}"),
+            RunError::Params(e) | RunError::Panic(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for RunError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RunError::Input(e) => Some(e),
+            RunError::Parse(e) => Some(e),
+            RunError::Params(_) | RunError::Panic(_) => None,
+        }
+    }
+}
+
+impl From<InputError> for RunError {
+    fn from(e: InputError) -> Self {
+        RunError::Input(e)
+    }
+}
+
+impl From<ParseError> for RunError {
+    fn from(e: ParseError) -> Self {
+        RunError::Parse(e)
+    }
+}
+
+/// Calls `f`, and says how long it took.
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let ret = f();
+    (ret, start.elapsed())
+}
+
+/// Parses `input` and solves `parts` of it, timing each step on its own.
+/// What each step allocates is counted if `alloc` is counting, and frames
+/// are drawn if `visual` is enabled. Parts taking longer than `timeout` are
+/// asked to give up, and those that do are marked as timed out. Nothing is solved if
+/// `params` don't suit the input.
+pub fn run_day(
+    solver: &dyn Solver,
+    input: &str,
+    parts: &[Part],
+    params: &Params,
+    timeout: Option<Duration>,
+) -> Result<DayRun, RunError> {
+    let ((parsed, parse), parse_alloc) = alloc::measure(|| timed(|| solver.parse(input)));
+    let parsed = parsed?;
+    solver.check(&parsed, params).map_err(RunError::Params)?;
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let cancel = Cancel::after(timeout);
+            let solve = || solver.solve(&parsed, part, params);
+            let visualized = || visual::scope(solver.year(), solver.day(), part, solve);
+            let ((answer, duration), alloc) = alloc::measure(|| timed(|| cancel.run(visualized)));
+            PartRun {
+                part,
+                answer,
+                duration,
+                alloc,
+                timed_out: cancel.heeded(),
+            }
+        })
+        .collect();
+    Ok(DayRun {
+        year: solver.year(),
+        day: solver.day(),
+        title: solver.title(),
+        parse,
+        parse_alloc,
+        parts,
+    })
+}
+
+/// Calls `f` on each item on up to `threads` threads, which take the next
+/// item as soon as they are done with one. The results are in item order.
+pub fn parallel_map<T, R>(items: &[T], threads: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    let threads = threads.clamp(1, items.len().max(1));
+    if threads == 1 {
+        return items.iter().map(f).collect();
+    }
+    let next = AtomicUsize::new(0);
+    let mut done: Vec<(usize, R)> = thread::scope(|s| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut ret = vec![];
+                    loop {
+                        let n = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(n) else {
+                            break;
+                        };
+                        ret.push((n, f(item)));
+                    }
+                    ret
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|w| w.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect()
+    });
+    done.sort_by_key(|(n, _)| *n);
+    done.into_iter().map(|(_, r)| r).collect()
+}
+
+/// Loads the input of each job's day and solves it, running up to `threads`
+/// days at the same time. A day that panics fails on its own, and the
+/// others still run.
+pub fn run_days(
+    registry: &Registry,
+    jobs: &[Job],
+    source: &Source,
+    threads: usize,
+) -> Vec<Result<DayRun, RunError>> {
+    parallel_map(jobs, threads, |job| {
+        let run = || {
+            let solver = registry
+                .get(job.year, job.day)
+                .expect("days come from the registry");
+            let input = input::load(job.year, job.day, source)?;
+            run_day(solver, &input, &job.parts, &job.params, job.timeout)
+        };
+        panic::catch_unwind(panic::AssertUnwindSafe(run)).unwrap_or_else(|e| {
+            let message = e
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| e.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("no message");
+            let label = day_label(job.year, job.day);
+            Err(RunError::Panic(format!("{label} panicked: {message}")))
+        })
+    })
+}
+
+/// `1.23s`, `45.67ms` or `890.12µs`.
+pub fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs_f64();
+    if secs >= 1. {
+        format!("{secs:.2}s")
+    } else if secs >= 1e-3 {
+        format!("{:.2}ms", secs * 1e3)
+    } else {
+        format!("{:.2}µs", secs * 1e6)
+    }
+}
+
+/// Summary table of the time taken by each day, with totals at the bottom.
+pub fn timing_table(runs: &[DayRun]) -> String {
+    let mut ret = String::new();
+    let labels: Vec<String> = runs
+        .iter()
+        .map(|r| short_day_label(r.year, r.day))
+        .collect();
+    let width = labels.iter().map(|l| l.len()).max().unwrap_or(0).max(3);
+    let mut row = |day: &str, title: &str, cols: [String; 4]| {
+        writeln!(
+            ret,
+            "{day:>width$}  {title:<24}{:>11}{:>11}{:>11}{:>11}",
+            cols[0], cols[1], cols[2], cols[3]
+        )
+        .unwrap();
+    };
+    let cells =
+        |ds: [Option<Duration>; 4]| ds.map(|d| d.map(format_duration).unwrap_or("-".to_string()));
+
+    row(
+        "day",
+        "",
+        ["parse", "part 1", "part 2", "total"].map(|h| h.to_string()),
+    );
+    let mut totals: [Option<Duration>; 4] = [None; 4];
+    for (run, label) in runs.iter().zip(labels.iter()) {
+        let durations = [
+            Some(run.parse),
+            run.part(Part::One).map(|p| p.duration),
+            run.part(Part::Two).map(|p| p.duration),
+            Some(run.total()),
+        ];
+        for (total, d) in totals.iter_mut().zip(durations) {
+            if let Some(d) = d {
+                *total = Some(total.unwrap_or_default() + d);
+            }
+        }
+        row(label, run.title, cells(durations));
+    }
+    row("", "total", cells(totals));
+    ret
+}
+
+/// What each step of each day allocated, for the runs that counted it.
+pub fn alloc_table(runs: &[DayRun]) -> String {
+    let mut ret = String::new();
+    let labels: Vec<String> = runs
+        .iter()
+        .map(|r| short_day_label(r.year, r.day))
+        .collect();
+    let width = labels.iter().map(|l| l.len()).max().unwrap_or(0).max(3);
+    let mut row = |day: &str, step: &str, cols: [String; 3]| {
+        writeln!(
+            ret,
+            "{day:>width$}  {step:<8}{:>13}{:>13}{:>13}",
+            cols[0], cols[1], cols[2]
+        )
+        .unwrap();
+    };
+    row(
+        "day",
+        "",
+        ["allocations", "allocated", "peak"].map(|h| h.to_string()),
+    );
+    for (run, label) in runs.iter().zip(labels.iter()) {
+        let steps = [("parse".to_string(), run.parse_alloc)].into_iter().chain(
+            run.parts
+                .iter()
+                .map(|p| (format!("part {}", p.part), p.alloc)),
+        );
+        let mut label = label.as_str();
+        for (step, stats) in steps {
+            if let Some(s) = stats {
+                let cols = [
+                    s.allocations.to_string(),
+                    format_bytes(s.bytes),
+                    format_bytes(s.peak),
+                ];
+                row(label, &step, cols);
+                label = "";
+            }
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::time::Duration;
+
+    use super::{
+        alloc_table, format_duration, parallel_map, run_day, run_days, timing_table, DayRun, Job,
+        PartRun, RunError,
+    };
+    use crate::{
+        alloc::AllocStats,
+        answer::Answer,
+        cancel,
+        input::Source,
+        parse::ParseError,
+        part::Part,
+        registry::{self, Registry},
+        solution::{Params, Solution},
+    };
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_millis(2310)), "2.31s");
+        assert_eq!(format_duration(Duration::from_micros(45670)), "45.67ms");
+        assert_eq!(format_duration(Duration::from_nanos(890120)), "890.12µs");
+    }
+
+    #[test]
+    fn test_run_day() {
+        let registry = registry::all();
+        let solver = registry.get(2024, 11).unwrap();
+        let blinks = Params::new(solver.params(), &[("blinks1".to_string(), 6)]).unwrap();
+        let run = run_day(solver, "125 17", &[Part::One], &blinks, None).unwrap();
+        assert_eq!(run.parts[0].answer, Answer::Int(22));
+        let run = run_day(
+            solver,
+            "125 17",
+            &[Part::One],
+            &Params::defaults(solver.params()),
+            None,
+        )
+        .unwrap();
+        assert_eq!(run.day, 11);
+        assert_eq!(run.parts.len(), 1);
+        assert_eq!(run.part(Part::One).unwrap().answer, Answer::Int(55312));
+        assert!(run.part(Part::Two).is_none());
+        assert!(run.total() >= run.parse);
+
+        let table = timing_table(&[run]);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with(" 11  Plutonian Pebbles"));
+        assert!(lines[1].contains(" -"));
+        assert!(lines[2].contains("total"));
+
+        let other_year = DayRun {
+            year: 2023,
+            day: 5,
+            title: "If You Give A Seed A Fertilizer",
+            parse: Duration::from_millis(1),
+            parse_alloc: None,
+            parts: vec![],
+        };
+        let table = timing_table(&[other_year]);
+        assert!(table
+            .lines()
+            .nth(1)
+            .unwrap()
+            .starts_with("2023/5  If You Give"));
+        assert!(table.lines().next().unwrap().starts_with("   day  "));
+
+        match run_day(solver, "125 x7", &[Part::One], &blinks, None) {
+            Err(RunError::Parse(e)) => {
+                assert_eq!((e.day, e.line, e.column), (Some((2024, 11)), 1, 5))
+            }
+            _ => panic!("`x7` is not a number"),
+        }
+    }
+
+    /// Only part 1, which has a bug.
+    struct Panics;
+
+    impl Solution for Panics {
+        type Input = ();
+        const DAY: usize = 27;
+        const TITLE: &'static str = "Panics";
+        const PARTS: &'static [Part] = &[Part::One];
+
+        fn parse(_input: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+
+        fn part1(_input: &Self::Input, _params: &Params) -> Answer {
+            panic!("a bug")
+        }
+    }
+
+    #[test]
+    fn test_run_days_panic() {
+        let mut registry = Registry::new();
+        registry.register(&Panics);
+        registry.register(registry::all().get(2024, 11).unwrap());
+        let jobs = [27, 11].map(|day| Job::all_parts(registry.get(2024, day).unwrap()));
+        let path =
+            std::env::temp_dir().join(format!("aoc_2024_run_days_panic_{}", std::process::id()));
+        std::fs::write(&path, "125 17\n").unwrap();
+        let source = Source::File(path.clone());
+        let runs: Vec<_> = [1, 2]
+            .map(|threads| run_days(&registry, &jobs, &source, threads))
+            .into_iter()
+            .collect();
+        std::fs::remove_file(&path).unwrap();
+        for runs in runs {
+            match &runs[0] {
+                Err(RunError::Panic(e)) => assert_eq!(e, "day 27 panicked: a bug"),
+                _ => panic!("day 27 panics"),
+            }
+            let run = runs[1].as_ref().unwrap();
+            assert_eq!(run.parts[0].answer, Answer::Int(55312));
+        }
+    }
+
+    /// Part 1 only stops when asked to, part 2 is late but never asks.
+    struct Forever;
+
+    impl Solution for Forever {
+        type Input = ();
+        const DAY: usize = 26;
+        const TITLE: &'static str = "Forever";
+
+        fn parse(_input: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+
+        fn part1(_input: &Self::Input, _params: &Params) -> Answer {
+            while !cancel::requested() {}
+            Answer::Int(1)
+        }
+
+        fn part2(_input: &Self::Input, _params: &Params) -> Answer {
+            std::thread::sleep(Duration::from_millis(30));
+            Answer::Int(2)
+        }
+    }
+
+    #[test]
+    fn test_timeout() {
+        let run = run_day(
+            &Forever,
+            "",
+            &[Part::One, Part::Two],
+            &Params::default(),
+            Some(Duration::from_millis(20)),
+        )
+        .unwrap();
+        let one = run.part(Part::One).unwrap();
+        assert!(one.timed_out);
+        assert!(one.duration >= Duration::from_millis(20));
+        // finished without being told to give up, so the answer is good
+        let two = run.part(Part::Two).unwrap();
+        assert!(two.duration >= Duration::from_millis(20));
+        assert!(!two.timed_out);
+        assert_eq!(two.answer, Answer::Int(2));
+    }
+
+    #[test]
+    fn test_alloc_table() {
+        let stats = |allocations, bytes, peak| {
+            Some(AllocStats {
+                allocations,
+                bytes,
+                peak,
+            })
+        };
+        let run = DayRun {
+            year: 2024,
+            day: 12,
+            title: "Garden Groups",
+            parse: Duration::from_millis(1),
+            parse_alloc: stats(3, 2048, 1024),
+            parts: vec![PartRun {
+                part: Part::Two,
+                answer: Answer::Int(1206),
+                duration: Duration::from_millis(2),
+                alloc: stats(1500, 3 << 20, 5000),
+                timed_out: false,
+            }],
+        };
+        let table = alloc_table(&[run]);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "day            allocations    allocated         peak",
+                " 12  parse               3      2.00KiB      1.00KiB",
+                "     part 2           1500      3.00MiB      4.88KiB",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parallel_map() {
+        let items: Vec<u64> = (0..50).collect();
+        let square = |n: &u64| {
+            // make later items finish first
+            std::thread::sleep(Duration::from_micros(50 - n));
+            n * n
+        };
+        let expected: Vec<u64> = items.iter().map(square).collect();
+        assert_eq!(parallel_map(&items, 4, square), expected);
+        assert_eq!(parallel_map(&items, 1, square), expected);
+        assert_eq!(parallel_map(&items, 100, square), expected);
+        assert_eq!(parallel_map(&[] as &[u64], 4, square), vec![]);
+    }
+
+    #[test]
+    fn test_run_days() {
+        let registry = registry::all();
+        let dir =
+            std::env::temp_dir().join(format!("aoc_2024_run_days_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("day_11"), "125 17\n").unwrap();
+        std::fs::write(dir.join("day_9"), "2333133121414131402\n").unwrap();
+        std::fs::write(dir.join("day_12"), "AA\nA\n").unwrap();
+        let jobs = [9, 10, 11, 12].map(|day| Job::all_parts(registry.get(2024, day).unwrap()));
+        let runs = run_days(&registry, &jobs, &Source::Dir(dir.clone()), 3);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(runs.len(), 4);
+        let run = runs[0].as_ref().unwrap();
+        assert_eq!((run.day, run.parts.len()), (9, 2));
+        assert_eq!(run.part(Part::Two).unwrap().answer, Answer::Int(2858));
+        match &runs[1] {
+            Err(RunError::Input(e)) => assert_eq!(e.day, 10),
+            _ => panic!("day 10 has no input"),
+        }
+        assert_eq!(
+            runs[2].as_ref().unwrap().parts[0].answer,
+            Answer::Int(55312)
+        );
+        match &runs[3] {
+            Err(RunError::Parse(e)) => assert_eq!((e.day, e.line), (Some((2024, 12)), 2)),
+            _ => panic!("day 12 input is not a rectangle"),
+        }
+    }
+}
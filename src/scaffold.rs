@@ -0,0 +1,449 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use aoc_2024::solution::DEFAULT_YEAR;
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// the day is there already, and this is where
+    Exists(PathBuf),
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    /// a file we need to change does not look as expected
+    Unexpected {
+        path: PathBuf,
+        what: String,
+    },
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::Exists(path) => {
+                write!(f, "{} already exists, not overwriting it", path.display())
+            }
+            ScaffoldError::Io { path, error } => write!(f, "{}: {error}", path.display()),
+            ScaffoldError::Unexpected { path, what } => {
+                write!(f, "can't find {what} in {}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+/// The module of a new day, with stubs that only count lines, and a test
+/// to fill with the example.
+pub fn module(year: usize, day: usize) -> String {
+    let text = TEMPLATE.replace("{day}", &day.to_string());
+    if year == DEFAULT_YEAR {
+        return text;
+    }
+    let day_const = format!("    const DAY: usize = {day};\n");
+    text.replace(
+        &day_const,
+        &format!("    const YEAR: usize = {year};\n{day_const}"),
+    )
+}
+
+const TEMPLATE: &str = r#"use crate::{
+    answer::Answer,
+    parse::{lines, ParseError},
+    solution::{Params, Solution},
+};
+
+fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    Ok(lines(input).iter().map(|l| l.text.to_string()).collect())
+}
+
+fn prob1(input: &[String]) -> usize {
+    input.len()
+}
+
+fn prob2(input: &[String]) -> usize {
+    input.len()
+}
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    type Input = Vec<String>;
+    const DAY: usize = {day};
+    const TITLE: &'static str = "Day {day}";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input, _params: &Params) -> Answer {
+        prob1(input).into()
+    }
+
+    fn part2(input: &Self::Input, _params: &Params) -> Answer {
+        prob2(input).into()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::{parse, prob1, prob2};
+
+    fn input() -> Vec<String> {
+        parse("").unwrap()
+    }
+
+    #[test]
+    fn test_prob1() {
+        assert_eq!(prob1(&input()), 0);
+    }
+
+    #[test]
+    fn test_prob2() {
+        assert_eq!(prob2(&input()), 0);
+    }
+}
+"#;
+
+const EXAMPLE_ANSWERS: &str = "\
+# the example from the puzzle text goes in example.txt
+part1 = 0
+part2 = 0
+";
+
+/// The module of a year other than the default one, registering its days.
+fn year_module(year: usize, day: usize) -> String {
+    format!(
+        "//! Solutions to the Advent of Code {year}.
+
+pub mod day_{day};
+
+use crate::registry::Registry;
+
+pub fn register(registry: &mut Registry) {{
+    registry.register(&day_{day}::Day{day});
+}}
+"
+    )
+}
+
+/// `lib` with `pub mod NAME;` among the other modules, in order.
+pub fn add_module(lib: &str, name: &str) -> Option<String> {
+    fn module_of(line: &str) -> Option<&str> {
+        line.strip_prefix("pub mod ")?.strip_suffix(';')
+    }
+    let mut lines: Vec<&str> = lib.lines().collect();
+    let first = lines.iter().position(|l| module_of(l).is_some())?;
+    let pos = (first..lines.len())
+        .find(|&n| module_of(lines[n]).is_none_or(|m| m > name))
+        .unwrap_or(lines.len());
+    let new = format!("pub mod {name};");
+    lines.insert(pos, &new);
+    Some(lines.join("\n") + "\n")
+}
+
+/// `use crate::{...};` with `items`, wrapped the way rustfmt does: on one
+/// line if it fits and nothing is nested, otherwise filling lines, with
+/// nested groups on lines of their own.
+fn use_crate(items: &[String]) -> String {
+    let nested = items.iter().any(|i| i.contains('{'));
+    let one_line = format!("use crate::{{{}}};", items.join(", "));
+    if one_line.len() <= 100 && !nested {
+        return one_line;
+    }
+    let mut ret = "use crate::{\n".to_string();
+    let mut line = String::new();
+    for item in items {
+        let alone = item.contains('{');
+        if !line.is_empty() && (alone || line.len() + item.len() + 2 > 99) {
+            ret += &line;
+            ret += "\n";
+            line.clear();
+        }
+        line += if line.is_empty() { "    " } else { " " };
+        line += item;
+        line += ",";
+        if alone {
+            ret += &line;
+            ret += "\n";
+            line.clear();
+        }
+    }
+    if !line.is_empty() {
+        ret += &line;
+        ret += "\n";
+    }
+    ret + "};"
+}
+
+/// The items of a `use` list, split at the commas outside nested groups.
+fn use_items(inner: &str) -> Vec<String> {
+    let mut ret = vec![];
+    let (mut depth, mut start) = (0, 0);
+    for (n, c) in inner.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                ret.push(inner[start..n].to_string());
+                start = n + 1;
+            }
+            _ => {}
+        }
+    }
+    ret.push(inner[start..].to_string());
+    ret.into_iter()
+        .map(|i| i.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|i| !i.is_empty())
+        .collect()
+}
+
+/// `text` with `item` added to its `use crate::{...};`.
+fn add_import(text: &str, item: &str) -> Option<String> {
+    let start = text.find("use crate::{")?;
+    let end = start + text[start..].find("};\n")? + 2;
+    let mut items = use_items(&text[start + "use crate::{".len()..end - 2]);
+    items.push(item.to_string());
+    items.sort();
+    Some(format!(
+        "{}{}{}",
+        &text[..start],
+        use_crate(&items),
+        &text[end..]
+    ))
+}
+
+/// `registry` importing `day_N` and registering `DayN` after the days before it.
+pub fn add_to_registry(registry: &str, day: usize) -> Option<String> {
+    add_register(&add_import(registry, &format!("day_{day}"))?, day)
+}
+
+/// `registry` importing `year_Y` and calling its `register` at the end of `all`.
+pub fn add_year_to_registry(registry: &str, year: usize) -> Option<String> {
+    let registry = add_import(registry, &format!("year_{year}"))?;
+    let mut lines: Vec<&str> = registry.lines().collect();
+    let pos = lines.iter().rposition(|l| *l == "    registry")?;
+    let new = format!("    year_{year}::register(&mut registry);");
+    lines.insert(pos, &new);
+    Some(lines.join("\n") + "\n")
+}
+
+/// `text` registering `DayN` after the days before it.
+fn add_register(text: &str, day: usize) -> Option<String> {
+    let day_of = |line: &str| -> Option<usize> {
+        line.trim()
+            .strip_prefix("registry.register(&day_")?
+            .split_once(':')?
+            .0
+            .parse()
+            .ok()
+    };
+    let mut lines: Vec<&str> = text.lines().collect();
+    let last_before = lines
+        .iter()
+        .rposition(|l| day_of(l).is_some_and(|d| d < day));
+    let pos = match last_before {
+        Some(n) => n + 1,
+        None => lines.iter().position(|l| day_of(l).is_some())?,
+    };
+    let new = format!("    registry.register(&day_{day}::Day{day});");
+    lines.insert(pos, &new);
+    Some(lines.join("\n") + "\n")
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|error| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    fs::write(path, contents).map_err(|error| ScaffoldError::Io {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+/// Adds day `day` of `year` to the crate at `root`: its module, registered,
+/// and an example to fill in. Days of other years than the default one go
+/// in a `year_Y` module, made on their first day. Nothing is written if any
+/// of it exists. Returns the files written.
+pub fn new_day(root: &Path, year: usize, day: usize) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let src = root.join("src");
+    let mut fixture_dir = root.join("fixtures");
+    let mut module_dir = src.clone();
+    if year != DEFAULT_YEAR {
+        fixture_dir.push(year.to_string());
+        module_dir.push(format!("year_{year}"));
+    }
+    fixture_dir.push(format!("day_{day}"));
+    let module_path = module_dir.join(format!("day_{day}.rs"));
+    let example = fixture_dir.join("example.txt");
+    let answers = fixture_dir.join("example.toml");
+    for path in [&module_path, &example, &answers] {
+        if path.exists() {
+            return Err(ScaffoldError::Exists(path.clone()));
+        }
+    }
+    let unexpected = |path: &Path, what: &str| ScaffoldError::Unexpected {
+        path: path.to_path_buf(),
+        what: what.to_string(),
+    };
+    let day_name = format!("day_{day}");
+    // the files that change, with their new contents
+    let mut changes: Vec<(PathBuf, String)> = vec![(module_path, module(year, day))];
+    let lib_path = src.join("lib.rs");
+    let registry_path = src.join("registry.rs");
+    let registered = "the days imported and registered";
+    if year == DEFAULT_YEAR {
+        let lib = read(&lib_path)?;
+        if lib.lines().any(|l| l == format!("pub mod {day_name};")) {
+            return Err(ScaffoldError::Exists(lib_path));
+        }
+        let lib =
+            add_module(&lib, &day_name).ok_or_else(|| unexpected(&lib_path, "`pub mod` lines"))?;
+        let registry = add_to_registry(&read(&registry_path)?, day)
+            .ok_or_else(|| unexpected(&registry_path, registered))?;
+        changes.push((lib_path, lib));
+        changes.push((registry_path, registry));
+    } else {
+        let year_path = module_dir.join("mod.rs");
+        if year_path.exists() {
+            let text = add_module(&read(&year_path)?, &day_name)
+                .and_then(|text| add_register(&text, day))
+                .ok_or_else(|| unexpected(&year_path, registered))?;
+            changes.push((year_path, text));
+        } else {
+            let lib = add_module(&read(&lib_path)?, &format!("year_{year}"))
+                .ok_or_else(|| unexpected(&lib_path, "`pub mod` lines"))?;
+            let registry = add_year_to_registry(&read(&registry_path)?, year)
+                .ok_or_else(|| unexpected(&registry_path, "the years imported and registered"))?;
+            changes.push((year_path, year_module(year, day)));
+            changes.push((lib_path, lib));
+            changes.push((registry_path, registry));
+        }
+    }
+
+    for dir in [&module_dir, &fixture_dir] {
+        fs::create_dir_all(dir).map_err(|error| ScaffoldError::Io {
+            path: dir.clone(),
+            error,
+        })?;
+    }
+    let mut written = vec![];
+    for (path, text) in changes {
+        write(&path, &text)?;
+        written.push(path);
+    }
+    write(&example, "")?;
+    write(&answers, EXAMPLE_ANSWERS)?;
+    written.extend([example, answers]);
+    Ok(written)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::fs;
+
+    use super::{
+        add_module, add_to_registry, add_year_to_registry, module, new_day, ScaffoldError,
+    };
+
+    const REGISTRY: &str = include_str!("registry.rs");
+
+    #[test]
+    fn test_add_module() {
+        let lib = "//! docs\n\npub mod answer;\npub mod day_1;\npub mod day_2;\npub mod utils;\n";
+        assert_eq!(
+            add_module(lib, "day_12").unwrap(),
+            "//! docs\n\npub mod answer;\npub mod day_1;\npub mod day_12;\npub mod day_2;\npub mod utils;\n"
+        );
+        assert!(add_module("//! nothing\n", "day_1").is_none());
+    }
+
+    #[test]
+    fn test_add_to_registry() {
+        // taking a day out and adding it back gives the same file
+        let without_9 = REGISTRY
+            .replace("    day_9,\n", "")
+            .replace("    registry.register(&day_9::Day9);\n", "");
+        assert_ne!(without_9, REGISTRY);
+        assert_eq!(add_to_registry(&without_9, 9).unwrap(), REGISTRY);
+
+        let small = "use crate::{day_1, day_3};\n\nfn all() {\n    registry.register(&day_1::Day1);\n    registry.register(&day_3::Day3);\n}\n";
+        assert_eq!(
+            add_to_registry(small, 2).unwrap(),
+            "use crate::{day_1, day_2, day_3};\n\nfn all() {\n    registry.register(&day_1::Day1);\n    registry.register(&day_2::Day2);\n    registry.register(&day_3::Day3);\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_add_year_to_registry() {
+        let small = "use crate::{day_1, solution::{day_label, Solver}};\n\nfn all() {\n    let mut registry = Registry::new();\n    registry.register(&day_1::Day1);\n    registry\n}\n";
+        assert_eq!(
+            add_year_to_registry(small, 2023).unwrap(),
+            "use crate::{\n    day_1,\n    solution::{day_label, Solver},\n    year_2023,\n};\n\nfn all() {\n    let mut registry = Registry::new();\n    registry.register(&day_1::Day1);\n    year_2023::register(&mut registry);\n    registry\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root =
+            std::env::temp_dir().join(format!("aoc_2024_scaffold_test_{}", std::process::id()));
+        // left behind by a run that failed, which would make it `Exists`
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), "pub mod day_1;\npub mod utils;\n").unwrap();
+        fs::write(
+            root.join("src/registry.rs"),
+            "use crate::{day_1};\n\nfn all() {\n    let mut registry = Registry::new();\n    registry.register(&day_1::Day1);\n    registry\n}\n",
+        )
+        .unwrap();
+        let written = new_day(&root, 2024, 2);
+        let module_2 = fs::read_to_string(root.join("src/day_2.rs"));
+        let again = new_day(&root, 2024, 2);
+        let first_of_2023 = new_day(&root, 2023, 3);
+        let second_of_2023 = new_day(&root, 2023, 1);
+        let lib = fs::read_to_string(root.join("src/lib.rs"));
+        let registry = fs::read_to_string(root.join("src/registry.rs"));
+        let year_2023 = fs::read_to_string(root.join("src/year_2023/mod.rs"));
+        let fixture = root.join("fixtures/2023/day_3/example.toml").is_file();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(written.unwrap().len(), 5);
+        assert_eq!(module_2.unwrap(), module(2024, 2));
+        match again {
+            Err(ScaffoldError::Exists(path)) => assert!(path.ends_with("src/day_2.rs")),
+            other => panic!("{other:?} should refuse to overwrite"),
+        }
+        assert_eq!(first_of_2023.unwrap().len(), 6);
+        assert_eq!(second_of_2023.unwrap().len(), 4);
+        assert_eq!(
+            lib.unwrap(),
+            "pub mod day_1;\npub mod day_2;\npub mod utils;\npub mod year_2023;\n"
+        );
+        assert!(registry.unwrap().contains(
+            "    registry.register(&day_2::Day2);\n    year_2023::register(&mut registry);\n"
+        ));
+        assert_eq!(
+            year_2023.unwrap(),
+            "//! Solutions to the Advent of Code 2023.\n\npub mod day_1;\npub mod day_3;\n\nuse crate::registry::Registry;\n\npub fn register(registry: &mut Registry) {\n    registry.register(&day_1::Day1);\n    registry.register(&day_3::Day3);\n}\n"
+        );
+        assert!(fixture);
+    }
+
+    #[test]
+    fn test_module() {
+        let text = module(2024, 26);
+        assert!(text.contains("pub struct Day26;"));
+        assert!(text.contains("const DAY: usize = 26;"));
+        assert!(!text.contains("{day}"));
+        assert!(!text.contains("const YEAR"));
+        let text = module(2023, 1);
+        assert!(text.contains("    const YEAR: usize = 2023;\n    const DAY: usize = 1;\n"));
+    }
+}
@@ -0,0 +1,277 @@
+use std::{any::Any, fmt};
+
+use crate::{answer::Answer, lint::Constraint, parse::ParseError, part::Part};
+
+/// The year of the days that don't say otherwise.
+pub const DEFAULT_YEAR: usize = 2024;
+
+/// `day 5`, or `2023 day 5` for any year but the default one.
+pub fn day_label(year: usize, day: usize) -> String {
+    if year == DEFAULT_YEAR {
+        format!("day {day}")
+    } else {
+        format!("{year} day {day}")
+    }
+}
+
+/// `5`, or `2023/5` for any year but the default one, where space is short.
+pub fn short_day_label(year: usize, day: usize) -> String {
+    if year == DEFAULT_YEAR {
+        day.to_string()
+    } else {
+        format!("{year}/{day}")
+    }
+}
+
+/// A day of the calendar: how to read its input and how to solve its problems.
+pub trait Solution {
+    /// What `parse` makes of the raw input, shared by both parts.
+    type Input: 'static;
+
+    const YEAR: usize = DEFAULT_YEAR;
+    const DAY: usize;
+    const TITLE: &'static str;
+    /// The parts this day has. Only day 25 has less than two.
+    const PARTS: &'static [Part] = &Part::BOTH;
+    /// Numbers given by the puzzle text rather than the input, which are
+    /// usually different for the examples.
+    const PARAMS: &'static [Param] = &[];
+    /// What the parser takes for granted about the input, for `lint` to
+    /// check.
+    const CONSTRAINTS: &'static [Constraint] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    /// `Err` saying why, when `params` can't be used on `input`. The parts
+    /// are only called when this is `Ok`.
+    fn check(_input: &Self::Input, _params: &Params) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn part1(input: &Self::Input, params: &Params) -> Answer;
+
+    /// Only called when `PARTS` contains `Part::Two`.
+    fn part2(_input: &Self::Input, _params: &Params) -> Answer {
+        unreachable!("day {} has no part 2", Self::DAY)
+    }
+}
+
+/// A named parameter of a day, with the value the real input needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: usize,
+    pub help: &'static str,
+}
+
+impl fmt::Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.name, self.default)
+    }
+}
+
+/// The values of the parameters of a day: their defaults, unless given.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Params {
+    values: Vec<(&'static str, usize)>,
+}
+
+impl Params {
+    pub fn defaults(declared: &'static [Param]) -> Self {
+        Params {
+            values: declared.iter().map(|p| (p.name, p.default)).collect(),
+        }
+    }
+
+    /// `Err` when something in `given` is not in `declared`.
+    pub fn new(declared: &'static [Param], given: &[(String, usize)]) -> Result<Self, String> {
+        let mut ret = Self::defaults(declared);
+        for (name, value) in given {
+            match ret.values.iter_mut().find(|(n, _)| n == name) {
+                Some((_, v)) => *v = *value,
+                None if declared.is_empty() => {
+                    return Err(format!("no parameter `{name}`. This day has none."))
+                }
+                None => {
+                    let names: Vec<&str> = declared.iter().map(|p| p.name).collect();
+                    return Err(format!(
+                        "no parameter `{name}`. Parameters are {}.",
+                        names.join(", ")
+                    ));
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    /// Panics if the day did not declare `name`.
+    pub fn get(&self, name: &str) -> usize {
+        match self.values.iter().find(|(n, _)| *n == name) {
+            Some((_, value)) => *value,
+            None => panic!("undeclared parameter `{name}`"),
+        }
+    }
+
+    /// `Err` unless each of `names` is at least 1, as sizes must be.
+    pub fn positive(&self, names: &[&str]) -> Result<(), String> {
+        match names.iter().find(|&&name| self.get(name) == 0) {
+            Some(name) => Err(format!("`{name}` must be at least 1")),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Whatever a `Solver` parsed, to be handed back to it.
+pub type Parsed = Box<dyn Any>;
+
+/// Object safe view of a `Solution`, so that days can be kept together
+/// in a registry and called without knowing their input types.
+pub trait Solver: Sync {
+    fn year(&self) -> usize;
+    fn day(&self) -> usize;
+    fn title(&self) -> &'static str;
+    fn parts(&self) -> &'static [Part];
+    fn params(&self) -> &'static [Param];
+    fn constraints(&self) -> &'static [Constraint];
+    /// The error says which day it comes from.
+    fn parse(&self, input: &str) -> Result<Parsed, ParseError>;
+    /// `parsed` must come from `self.parse`. The error says which day it
+    /// comes from.
+    fn check(&self, parsed: &Parsed, params: &Params) -> Result<(), String>;
+    /// `parsed` must come from `self.parse`, and pass `self.check`.
+    fn solve(&self, parsed: &Parsed, part: Part, params: &Params) -> Answer;
+}
+
+impl<S> Solver for S
+where
+    S: Solution + Sync,
+{
+    fn year(&self) -> usize {
+        S::YEAR
+    }
+
+    fn day(&self) -> usize {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        S::PARTS
+    }
+
+    fn params(&self) -> &'static [Param] {
+        S::PARAMS
+    }
+
+    fn constraints(&self) -> &'static [Constraint] {
+        S::CONSTRAINTS
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed, ParseError> {
+        match S::parse(input) {
+            Ok(input) => Ok(Box::new(input)),
+            Err(e) => Err(e.in_day(S::YEAR, S::DAY)),
+        }
+    }
+
+    fn check(&self, parsed: &Parsed, params: &Params) -> Result<(), String> {
+        S::check(parsed_input::<S>(parsed), params)
+            .map_err(|e| format!("{}: {e}", day_label(S::YEAR, S::DAY)))
+    }
+
+    fn solve(&self, parsed: &Parsed, part: Part, params: &Params) -> Answer {
+        let input = parsed_input::<S>(parsed);
+        match part {
+            Part::One => S::part1(input, params),
+            Part::Two => S::part2(input, params),
+        }
+    }
+}
+
+fn parsed_input<S: Solution>(parsed: &Parsed) -> &S::Input {
+    parsed
+        .downcast_ref::<S::Input>()
+        .unwrap_or_else(|| panic!("input of day {} was not parsed by it", S::DAY))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::{day_label, short_day_label, Param, Params, Solution, Solver};
+    use crate::{
+        answer::Answer,
+        parse::{lines, ParseError},
+        part::Part,
+    };
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<usize>;
+        const DAY: usize = 26;
+        const TITLE: &'static str = "Sum";
+        const PARTS: &'static [Part] = &[Part::One];
+        const PARAMS: &'static [Param] = &[Param {
+            name: "times",
+            default: 1,
+            help: "how many times to add everything",
+        }];
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            lines(input).iter().map(|l| l.number(l.text)).collect()
+        }
+
+        fn check(_input: &Self::Input, params: &Params) -> Result<(), String> {
+            params.positive(&["times"])
+        }
+
+        fn part1(input: &Self::Input, params: &Params) -> Answer {
+            (params.get("times") * input.iter().sum::<usize>()).into()
+        }
+    }
+
+    #[test]
+    fn test_solver() {
+        let solver: &dyn Solver = &Sum;
+        assert_eq!((solver.year(), solver.day()), (2024, 26));
+        assert_eq!(solver.title(), "Sum");
+        assert_eq!(solver.parts(), &[Part::One]);
+        let parsed = solver.parse("1\n2\n3\n").unwrap();
+        let params = Params::defaults(solver.params());
+        assert_eq!(solver.solve(&parsed, Part::One, &params), Answer::Int(6));
+        let params = Params::new(solver.params(), &[("times".to_string(), 3)]).unwrap();
+        assert_eq!(solver.solve(&parsed, Part::One, &params), Answer::Int(18));
+        let params = Params::new(solver.params(), &[("times".to_string(), 0)]).unwrap();
+        assert_eq!(
+            solver.check(&parsed, &params),
+            Err("day 26: `times` must be at least 1".to_string())
+        );
+        let e = solver.parse("1\nx\n").err().unwrap();
+        assert_eq!(
+            e.to_string(),
+            "day 26, line 2, column 1: expected a number, found `x`"
+        );
+    }
+
+    #[test]
+    fn test_day_label() {
+        assert_eq!(day_label(2024, 5), "day 5");
+        assert_eq!(day_label(2023, 5), "2023 day 5");
+        assert_eq!(short_day_label(2024, 5), "5");
+        assert_eq!(short_day_label(2023, 5), "2023/5");
+    }
+
+    #[test]
+    fn test_params() {
+        let declared = Sum::PARAMS;
+        assert_eq!(Params::defaults(declared).get("times"), 1);
+        assert_eq!(declared[0].to_string(), "times=1");
+        assert_eq!(
+            Params::new(declared, &[("twice".to_string(), 2)]),
+            Err("no parameter `twice`. Parameters are times.".to_string())
+        );
+        assert!(Params::new(&[], &[("times".to_string(), 2)]).is_err());
+    }
+}
@@ -1,11 +1,15 @@
-use num::{Bounded, Zero};
+use num::Zero;
 use std::{
+    array,
     cmp::Reverse,
-    collections::{BinaryHeap, HashMap, HashSet},
-    fmt,
-    ops::Add,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    fmt, iter,
+    ops::{Add, Mul, Neg, Sub},
+    str::FromStr,
 };
 
+use crate::parse::{Line, ParseError};
+
 pub fn counter<I>(ns: I) -> HashMap<I::Item, usize>
 where
     I: IntoIterator,
@@ -18,20 +22,25 @@ where
     ret
 }
 
+/// A directed graph. Nodes get ids in the order they are added, and each
+/// one keeps the ids of its children and of its parents.
+#[derive(Debug, Clone)]
 pub struct DiGraph<I> {
-    pub nodes: Vec<I>,
-    pub edges: Vec<(I, I)>,
+    nodes: Vec<I>,
+    ids: HashMap<I, usize>,
+    children: Vec<Vec<usize>>,
+    parents: Vec<Vec<usize>>,
+    edges: usize,
 }
 
 impl<I> DiGraph<I> {
-    pub fn from<II, EE>(nodes: II, edges: EE) -> DiGraph<I>
-    where
-        II: IntoIterator<Item = I>,
-        EE: IntoIterator<Item = (I, I)>,
-    {
+    pub fn new() -> Self {
         Self {
-            nodes: nodes.into_iter().collect(),
-            edges: edges.into_iter().collect(),
+            nodes: vec![],
+            ids: HashMap::new(),
+            children: vec![],
+            parents: vec![],
+            edges: 0,
         }
     }
 
@@ -43,60 +52,148 @@ impl<I> DiGraph<I> {
         self.nodes.is_empty()
     }
 
-    fn children(&self, from: I) -> HashSet<I>
-    where
-        I: Eq + core::hash::Hash + Copy,
-    {
+    /// In the order they were added.
+    pub fn nodes(&self) -> &[I] {
+        &self.nodes
+    }
+
+    pub fn edge_count(&self) -> usize {
         self.edges
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (&I, &I)> {
+        self.children
             .iter()
-            .filter(|&(f, _)| f == &from)
-            .map(|&(_, t)| t)
-            .collect()
+            .enumerate()
+            .flat_map(move |(a, cs)| cs.iter().map(move |&b| (&self.nodes[a], &self.nodes[b])))
+    }
+}
+
+impl<I> Default for DiGraph<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I> DiGraph<I>
+where
+    I: Eq + std::hash::Hash + Clone,
+{
+    pub fn from<II, EE>(nodes: II, edges: EE) -> DiGraph<I>
+    where
+        II: IntoIterator<Item = I>,
+        EE: IntoIterator<Item = (I, I)>,
+    {
+        let mut ret = Self::new();
+        for node in nodes {
+            ret.add_node(node);
+        }
+        for (a, b) in edges {
+            ret.add_edge(a, b);
+        }
+        ret
+    }
+
+    /// The id of `node`, which is added if it wasn't there.
+    pub fn add_node(&mut self, node: I) -> usize {
+        if let Some(&id) = self.ids.get(&node) {
+            return id;
+        }
+        let id = self.nodes.len();
+        self.ids.insert(node.clone(), id);
+        self.nodes.push(node);
+        self.children.push(vec![]);
+        self.parents.push(vec![]);
+        id
+    }
+
+    /// Adds the edge and whichever of its nodes is missing. False if the
+    /// edge was already there.
+    pub fn add_edge(&mut self, from: I, to: I) -> bool {
+        let (a, b) = (self.add_node(from), self.add_node(to));
+        if self.children[a].contains(&b) {
+            return false;
+        }
+        self.children[a].push(b);
+        self.parents[b].push(a);
+        self.edges += 1;
+        true
+    }
+
+    pub fn contains(&self, node: &I) -> bool {
+        self.ids.contains_key(node)
+    }
+
+    pub fn contains_edge(&self, from: &I, to: &I) -> bool {
+        match (self.ids.get(from), self.ids.get(to)) {
+            (Some(&a), Some(b)) => self.children[a].contains(b),
+            _ => false,
+        }
+    }
+
+    fn linked<'a>(&'a self, node: &I, links: &'a [Vec<usize>]) -> impl Iterator<Item = &'a I> {
+        let ids = self.ids.get(node).map_or(&[][..], |&id| &links[id]);
+        ids.iter().map(|&id| &self.nodes[id])
+    }
+
+    /// Where the edges from `node` go, in the order they were added.
+    pub fn children(&self, node: &I) -> impl Iterator<Item = &I> {
+        self.linked(node, &self.children)
+    }
+
+    /// Where the edges to `node` come from, in the order they were added.
+    pub fn parents(&self, node: &I) -> impl Iterator<Item = &I> {
+        self.linked(node, &self.parents)
+    }
+
+    /// How many edges go from `node`.
+    pub fn degree(&self, node: &I) -> usize {
+        self.ids.get(node).map_or(0, |&id| self.children[id].len())
+    }
+
+    /// For each node reachable from `start`, including it, how many edges
+    /// away it is.
+    fn bfs(&self, start: usize) -> Vec<Option<usize>> {
+        let mut ret: Vec<Option<usize>> = vec![None; self.len()];
+        ret[start] = Some(0);
+        let mut queue = VecDeque::from([start]);
+        while let Some(a) = queue.pop_front() {
+            let next = ret[a].map(|d| d + 1);
+            for &b in self.children[a].iter() {
+                if ret[b].is_none() {
+                    ret[b] = next;
+                    queue.push_back(b);
+                }
+            }
+        }
+        ret
     }
 }
 
 impl<I> DiGraph<I>
 where
-    I: Eq + core::hash::Hash + Copy,
+    I: Eq + std::hash::Hash + Copy,
 {
     pub fn distance(&self, start: I, end: I) -> Option<usize> {
         if start == end {
             return Some(0);
         }
-        let mut visited: HashMap<I, usize> = HashMap::new();
-        let mut last_visited: HashSet<I> = HashSet::from([start]);
-        let mut distance = 0usize;
-        while !last_visited.is_empty() {
-            let mut next_visited: HashSet<I> = last_visited
-                .iter()
-                .flat_map(|&n| self.children(n))
-                .collect();
-            if next_visited.contains(&end) {
-                return Some(distance + 1);
-            }
-            next_visited = next_visited
-                .difference(&visited.keys().copied().collect())
-                .copied()
-                .collect();
-            last_visited.iter().for_each(|&n| {
-                visited.entry(n).or_insert(distance);
-            });
-            last_visited = next_visited;
-            distance += 1;
-        }
-        None
+        let (&a, &b) = (self.ids.get(&start)?, self.ids.get(&end)?);
+        self.bfs(a)[b]
     }
 
-    pub fn distances_from(&self, start: I) -> HashMap<I, usize>
-    where
-        I: std::fmt::Debug + Ord,
-    {
-        let weights: HashMap<(I, I), usize> =
-            self.edges.iter().map(|&(a, b)| ((a, b), 1)).collect();
-        self.distance_with(start, self.nodes.clone(), weights)
+    pub fn distances_from(&self, start: I) -> HashMap<I, usize> {
+        let Some(&a) = self.ids.get(&start) else {
+            return HashMap::new();
+        };
+        self.bfs(a)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(b, d)| Some((self.nodes[b], d?)))
+            .collect()
     }
 
-    /// disregards the nodes
+    /// disregards the nodes and edges, and goes along the keys of `weights`
     pub fn distance_with<II, V>(
         &self,
         start: I,
@@ -105,55 +202,46 @@ where
     ) -> HashMap<I, V>
     where
         II: IntoIterator<Item = I>,
-        I: std::fmt::Debug + Ord,
-        V: Add + Bounded + Zero + Ord + Copy + std::fmt::Debug,
+        I: Ord,
+        V: Add + Zero + Ord + Copy,
     {
-        // optimization: get children in weights fast
-        let mut wchildren: HashMap<I, HashSet<I>> = HashMap::new();
-        for (f, t) in weights.keys().copied() {
-            wchildren.entry(f).or_default().insert(t);
+        let mut wchildren: HashMap<I, Vec<(I, V)>> = HashMap::new();
+        for (&(f, t), &w) in weights.iter() {
+            wchildren.entry(f).or_default().push((t, w));
         }
         let ends: HashSet<I> = ends.into_iter().collect();
         let mut ret: HashMap<I, V> = HashMap::new();
-        let infinity = Reverse(V::max_value());
-        let mut unvisited: BinaryHeap<(Reverse<V>, I)> =
-            self.nodes.iter().map(|&n| (infinity, n)).collect();
+        let mut unvisited: BinaryHeap<(Reverse<V>, I)> = BinaryHeap::new();
         let mut visited: HashSet<I> = HashSet::new();
         unvisited.push((Reverse(V::zero()), start));
-        while !unvisited.is_empty() && unvisited.peek().unwrap().0 > infinity {
-            let (rdist, node) = unvisited.pop().unwrap();
-            if visited.contains(&node) {
+        while let Some((Reverse(dist), node)) = unvisited.pop() {
+            if !visited.insert(node) {
                 continue;
             }
-            let Reverse(dist) = rdist;
             if ends.contains(&node) {
                 ret.insert(node, dist);
                 if ret.len() == ends.len() {
                     break;
                 }
             }
-            for &child in wchildren.entry(node).or_default().iter() {
-                let &ndist = weights.get(&(node, child)).unwrap();
+            for &(child, ndist) in wchildren.get(&node).into_iter().flatten() {
                 unvisited.push((Reverse(dist + ndist), child));
             }
-            visited.insert(node);
         }
         ret
     }
 
+    #[allow(clippy::needless_range_loop)]
     pub fn all_distances(&self) -> Distances<I> {
-        let num: HashMap<I, usize> = self
-            .nodes
-            .iter()
-            .enumerate()
-            .map(|(i, &ij)| (ij, i))
-            .collect();
         let mut ret: Vec<Vec<usize>> = vec![vec![usize::MAX; self.len()]; self.len()];
-        for a in 0..self.len() {
-            ret[a][a] = 0;
+        for (a, children) in self.children.iter().enumerate() {
+            for &b in children {
+                ret[a][b] = 1;
+            }
         }
-        for &(a, b) in self.edges.iter() {
-            ret[num[&a]][num[&b]] = 1;
+        // after the edges, which may go from a node to itself
+        for (a, row) in ret.iter_mut().enumerate() {
+            row[a] = 0;
         }
         for b in 0..ret.len() {
             for a in 0..ret.len() {
@@ -170,7 +258,10 @@ where
                 }
             }
         }
-        Distances { num, dist: ret }
+        Distances {
+            num: self.ids.clone(),
+            dist: ret,
+        }
     }
 }
 
@@ -192,6 +283,196 @@ where
     }
 }
 
+/// Where to go on a grid of rows going down and columns going right.
+/// Turning right is clockwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, std::hash::Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Clockwise from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    fn turned(self, quarters: usize) -> Self {
+        Self::ALL[(self as usize + quarters) % 4]
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.turned(1)
+    }
+
+    pub fn turn_around(self) -> Self {
+        self.turned(2)
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.turned(3)
+    }
+
+    /// What a step this way adds to an `IJ`.
+    pub fn offset(self) -> IJ {
+        Direction8::from(self).offset()
+    }
+
+    /// `^`, `>`, `v` or `<`, as in the puzzles.
+    pub fn arrow(self) -> char {
+        ['^', '>', 'v', '<'][self as usize]
+    }
+
+    /// From `^>v<`, `NESW` or `↑→↓←`.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' | 'N' | '↑' => Some(Direction::North),
+            '>' | 'E' | '→' => Some(Direction::East),
+            'v' | 'S' | '↓' => Some(Direction::South),
+            '<' | 'W' | '←' => Some(Direction::West),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next().and_then(Self::from_char), chars.next()) {
+            (Some(d), None) => Ok(d),
+            _ => Err(format!("`{s}` is not one of `^>v<`, `NESW` or `↑→↓←`")),
+        }
+    }
+}
+
+/// A `Direction` or a diagonal. Turning right is clockwise, by 45 degrees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, std::hash::Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// Clockwise from north.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+    const NAMES: [&'static str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+    const ARROWS: [char; 8] = ['↑', '↗', '→', '↘', '↓', '↙', '←', '↖'];
+
+    fn turned(self, eighths: usize) -> Self {
+        Self::ALL[(self as usize + eighths) % 8]
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.turned(1)
+    }
+
+    pub fn turn_around(self) -> Self {
+        self.turned(4)
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.turned(7)
+    }
+
+    pub fn offset(self) -> IJ {
+        let (i, j) = [
+            (-1, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+            (1, 0),
+            (1, -1),
+            (0, -1),
+            (-1, -1),
+        ][self as usize];
+        IJ { i, j }
+    }
+
+    /// `None` for diagonals.
+    pub fn straight(self) -> Option<Direction> {
+        (self as usize)
+            .is_multiple_of(2)
+            .then(|| Direction::ALL[self as usize / 2])
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(d: Direction) -> Self {
+        Self::ALL[d as usize * 2]
+    }
+}
+
+impl fmt::Display for Direction8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Self::NAMES[*self as usize])
+    }
+}
+
+/// From `N`, `NE` and so on, an arrow like `↗`, or `^>v<`.
+impl FromStr for Direction8 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let found = Self::NAMES
+            .iter()
+            .position(|&name| name == s)
+            .or_else(|| Self::ARROWS.iter().position(|a| s == a.to_string()))
+            .map(|n| Self::ALL[n]);
+        match found {
+            Some(d) => Ok(d),
+            None => s
+                .parse::<Direction>()
+                .map(Self::from)
+                .map_err(|_| format!("`{s}` is not a direction like `N`, `NE` or `↗`")),
+        }
+    }
+}
+
+/// One step from `self` in that direction.
+impl Add<Direction> for IJ {
+    type Output = IJ;
+
+    fn add(self, d: Direction) -> IJ {
+        self + Direction8::from(d)
+    }
+}
+
+impl Add<Direction8> for IJ {
+    type Output = IJ;
+
+    fn add(self, d: Direction8) -> IJ {
+        self + d.offset()
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, std::hash::Hash)]
 pub struct IJ {
     pub i: isize,
@@ -204,32 +485,34 @@ impl IJ {
             j: j as isize,
         }
     }
-    fn left(&self) -> Self {
-        Self {
-            i: self.i,
-            j: self.j - 1,
-        }
+    /// Manhattan distance.
+    pub fn distance(&self, other: &IJ) -> isize {
+        (self.i - other.i).abs() + (self.j - other.j).abs()
     }
-    fn up(&self) -> Self {
-        Self {
-            i: self.i - 1,
-            j: self.j,
-        }
+    /// Chebyshev distance: the steps a king needs, diagonals included.
+    pub fn chebyshev(&self, other: &IJ) -> isize {
+        (self.i - other.i).abs().max((self.j - other.j).abs())
     }
-    fn right(&self) -> Self {
-        Self {
-            i: self.i,
-            j: self.j + 1,
+    /// Each coordinate wrapped into `0..size`, as on a torus.
+    pub fn rem_euclid(&self, size: IJ) -> IJ {
+        IJ {
+            i: self.i.rem_euclid(size.i),
+            j: self.j.rem_euclid(size.j),
         }
     }
-    fn down(&self) -> Self {
-        Self {
-            i: self.i + 1,
-            j: self.j,
+    /// Turned 90 degrees clockwise around the origin, so that north goes
+    /// east.
+    pub fn rotate_right(&self) -> IJ {
+        IJ {
+            i: self.j,
+            j: -self.i,
         }
     }
-    pub fn distance(&self, other: &IJ) -> isize {
-        (self.i - other.i).abs() + (self.j - other.j).abs()
+    pub fn rotate_left(&self) -> IJ {
+        IJ {
+            i: -self.j,
+            j: self.i,
+        }
     }
     pub fn closer_than(&self, max_dist: isize) -> impl Iterator<Item = IJ> + use<'_> {
         (-max_dist..=max_dist).flat_map(move |i| {
@@ -254,7 +537,8 @@ impl IJ {
     }
 
     pub fn neighbors(&self) -> [IJ; 4] {
-        [self.left(), self.up(), self.right(), self.down()]
+        use Direction::*;
+        [West, North, East, South].map(|d| *self + d)
     }
 }
 impl fmt::Display for IJ {
@@ -263,6 +547,202 @@ impl fmt::Display for IJ {
     }
 }
 
+impl From<(isize, isize)> for IJ {
+    fn from((i, j): (isize, isize)) -> Self {
+        IJ { i, j }
+    }
+}
+
+impl From<IJ> for (isize, isize) {
+    fn from(ij: IJ) -> Self {
+        (ij.i, ij.j)
+    }
+}
+
+impl Add for IJ {
+    type Output = IJ;
+
+    fn add(self, other: IJ) -> IJ {
+        IJ {
+            i: self.i + other.i,
+            j: self.j + other.j,
+        }
+    }
+}
+
+impl Sub for IJ {
+    type Output = IJ;
+
+    fn sub(self, other: IJ) -> IJ {
+        IJ {
+            i: self.i - other.i,
+            j: self.j - other.j,
+        }
+    }
+}
+
+impl Neg for IJ {
+    type Output = IJ;
+
+    fn neg(self) -> IJ {
+        IJ {
+            i: -self.i,
+            j: -self.j,
+        }
+    }
+}
+
+impl Mul<isize> for IJ {
+    type Output = IJ;
+
+    fn mul(self, k: isize) -> IJ {
+        IJ {
+            i: k * self.i,
+            j: k * self.j,
+        }
+    }
+}
+
+impl Mul<IJ> for isize {
+    type Output = IJ;
+
+    fn mul(self, ij: IJ) -> IJ {
+        ij * self
+    }
+}
+
+/// A point with `N` coordinates, for puzzles in more dimensions than the
+/// two of `IJ`, which converts to and from `Point<2>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, std::hash::Hash)]
+pub struct Point<const N: usize>(pub [isize; N]);
+
+/// Every offset with coordinates in `-d..=d`, in lexicographic order.
+fn cube<const N: usize>(d: isize) -> impl Iterator<Item = Point<N>> {
+    let mut next = (d >= 0).then_some([-d; N]);
+    iter::from_fn(move || {
+        let current = next?;
+        next = None;
+        let mut o = current;
+        for k in (0..N).rev() {
+            if o[k] < d {
+                o[k] += 1;
+                next = Some(o);
+                break;
+            }
+            o[k] = -d;
+        }
+        Some(Point(current))
+    })
+}
+
+impl<const N: usize> Point<N> {
+    pub const ORIGIN: Self = Point([0; N]);
+
+    /// Manhattan distance.
+    pub fn distance(&self, other: &Self) -> isize {
+        (0..N).map(|k| (self.0[k] - other.0[k]).abs()).sum()
+    }
+
+    /// Chebyshev distance.
+    pub fn chebyshev(&self, other: &Self) -> isize {
+        (0..N)
+            .map(|k| (self.0[k] - other.0[k]).abs())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The `2 * N` points one step away along an axis.
+    pub fn neighbors(&self) -> impl Iterator<Item = Self> + use<'_, N> {
+        cube(1)
+            .filter(|o| o.distance(&Self::ORIGIN) == 1)
+            .map(|o| *self + o)
+    }
+
+    /// The `3^N - 1` points around, diagonals included.
+    pub fn neighbors_diagonal(&self) -> impl Iterator<Item = Self> + use<'_, N> {
+        cube(1).filter(|&o| o != Self::ORIGIN).map(|o| *self + o)
+    }
+
+    /// The points at a Manhattan distance of at most `max_dist`. Goes
+    /// through the cube around them, so it is slower than `IJ`'s.
+    pub fn closer_than(&self, max_dist: isize) -> impl Iterator<Item = Self> + use<'_, N> {
+        cube(max_dist)
+            .filter(move |o| o.distance(&Self::ORIGIN) <= max_dist)
+            .map(|o| *self + o)
+    }
+
+    /// Whether `min <= self < max` in every coordinate.
+    pub fn inside(&self, min: Self, max: Self) -> bool {
+        (0..N).all(|k| min.0[k] <= self.0[k] && self.0[k] < max.0[k])
+    }
+
+    pub fn closer_than_and_bounded(
+        &self,
+        max_dist: isize,
+        min: Self,
+        max: Self,
+    ) -> impl Iterator<Item = Self> + use<'_, N> {
+        self.closer_than(max_dist)
+            .filter(move |p| p.inside(min, max))
+    }
+
+    /// Each coordinate wrapped into `0..size`.
+    pub fn rem_euclid(&self, size: Self) -> Self {
+        Point(array::from_fn(|k| self.0[k].rem_euclid(size.0[k])))
+    }
+}
+
+impl<const N: usize> Add for Point<N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point(array::from_fn(|k| self.0[k] + other.0[k]))
+    }
+}
+
+impl<const N: usize> Sub for Point<N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point(array::from_fn(|k| self.0[k] - other.0[k]))
+    }
+}
+
+impl<const N: usize> Neg for Point<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point(self.0.map(|x| -x))
+    }
+}
+
+impl<const N: usize> Mul<isize> for Point<N> {
+    type Output = Self;
+
+    fn mul(self, k: isize) -> Self {
+        Point(self.0.map(|x| k * x))
+    }
+}
+
+impl<const N: usize> fmt::Display for Point<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let coordinates: Vec<String> = self.0.iter().map(|x| x.to_string()).collect();
+        write!(f, "({})", coordinates.join(", "))
+    }
+}
+
+impl From<IJ> for Point<2> {
+    fn from(ij: IJ) -> Self {
+        Point([ij.i, ij.j])
+    }
+}
+
+impl From<Point<2>> for IJ {
+    fn from(Point([i, j]): Point<2>) -> Self {
+        IJ { i, j }
+    }
+}
+
 pub struct Maze {
     pub width: isize,
     pub height: isize,
@@ -353,7 +833,147 @@ impl Maze {
                     .collect::<Vec<_>>()
             })
             .collect();
-        DiGraph { nodes, edges }
+        DiGraph::from(nodes, edges)
+    }
+}
+
+/// A rectangle of cells, kept row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// `height` rows of `width` times `value`.
+    pub fn new(height: usize, width: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            height,
+            width,
+            cells: vec![value; height * width],
+        }
+    }
+
+    /// `lines` as rows, with `cell` saying what each character is, or `None`
+    /// if it is not `expected`. Rows must be as long as the first one.
+    pub fn parse(
+        lines: &[Line],
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let Some(first) = lines.first() else {
+            return Err(ParseError::missing(lines, "a grid"));
+        };
+        let width = first.text.chars().count();
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for line in lines {
+            for (n, c) in line.text.char_indices() {
+                match cell(c) {
+                    Some(value) => cells.push(value),
+                    None => return Err(line.error_at(&line.text[n..n + c.len_utf8()], expected)),
+                }
+            }
+            if line.text.chars().count() != width {
+                return Err(line.error(format!("a row of {width} characters")));
+            }
+        }
+        Ok(Self {
+            height: lines.len(),
+            width,
+            cells,
+        })
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn contains(&self, ij: IJ) -> bool {
+        ij.inside(IJ { i: 0, j: 0 }, IJ::from_usize(self.height, self.width))
+    }
+
+    fn index(&self, ij: IJ) -> Option<usize> {
+        self.contains(ij)
+            .then(|| ij.i as usize * self.width + ij.j as usize)
+    }
+
+    /// `None` outside the grid.
+    pub fn get(&self, ij: IJ) -> Option<&T> {
+        self.index(ij).map(|n| &self.cells[n])
+    }
+
+    pub fn get_mut(&mut self, ij: IJ) -> Option<&mut T> {
+        self.index(ij).map(|n| &mut self.cells[n])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Top to bottom, empty if `j` is outside.
+    pub fn column(&self, j: usize) -> impl Iterator<Item = &T> {
+        self.rows().filter_map(move |row| row.get(j))
+    }
+
+    /// Every cell with where it is, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = (IJ, &T)> {
+        let width = self.width.max(1);
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(n, value)| (IJ::from_usize(n / width, n % width), value))
+    }
+
+    /// The cells left, up, right and down of `ij` that are in the grid.
+    pub fn neighbors4(&self, ij: IJ) -> impl Iterator<Item = IJ> + use<'_, T> {
+        ij.neighbors().into_iter().filter(|&n| self.contains(n))
+    }
+
+    /// The cells around `ij`, diagonals included, that are in the grid.
+    pub fn neighbors8(&self, ij: IJ) -> impl Iterator<Item = IJ> + use<'_, T> {
+        Direction8::ALL
+            .into_iter()
+            .map(move |d| ij + d)
+            .filter(|&n| self.contains(n))
+    }
+
+    /// Where the cells with `value` are, row by row.
+    pub fn positions<'a>(&'a self, value: &'a T) -> impl Iterator<Item = IJ> + 'a
+    where
+        T: PartialEq,
+    {
+        self.cells()
+            .filter(move |&(_, v)| v == value)
+            .map(|(ij, _)| ij)
+    }
+
+    /// The first cell with `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<IJ>
+    where
+        T: PartialEq,
+    {
+        self.positions(value).next()
+    }
+}
+
+/// Each row on a line.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for value in row {
+                write!(f, "{value}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
     }
 }
 
@@ -361,13 +981,123 @@ impl Maze {
 pub mod tests {
     use std::collections::{HashMap, HashSet};
 
-    use crate::utils::IJ;
+    use crate::{
+        differential::{assert_agree, shrink_vec, Rng},
+        utils::IJ,
+    };
 
-    use super::{DiGraph, Maze};
+    use super::{DiGraph, Direction, Direction8, Grid, Maze, Point};
+    use crate::parse::lines;
+
+    /// A random maze of up to 6 by 6, with `S` and `E` in different cells.
+    fn random_maze(rng: &mut Rng) -> Vec<String> {
+        let (height, width) = (rng.between(1, 6), rng.between(2, 6));
+        let mut rows: Vec<Vec<char>> = (0..height)
+            .map(|_| {
+                (0..width)
+                    .map(|_| if rng.one_in(3) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        let cell = |rng: &mut Rng| (rng.between(0, height - 1), rng.between(0, width - 1));
+        let (si, sj) = cell(rng);
+        let (ei, ej) = loop {
+            let ij = cell(rng);
+            if ij != (si, sj) {
+                break ij;
+            }
+        };
+        rows[si][sj] = 'S';
+        rows[ei][ej] = 'E';
+        rows.into_iter().map(|r| r.into_iter().collect()).collect()
+    }
+
+    /// The maze with a wall less, or without a row or column that has
+    /// neither `S` nor `E`.
+    fn shrink_maze(maze: &[String]) -> Vec<Vec<String>> {
+        let mut ret = vec![];
+        let free = |s: &str| !s.contains(['S', 'E']);
+        for (i, row) in maze.iter().enumerate() {
+            if free(row) {
+                let mut smaller = maze.to_vec();
+                smaller.remove(i);
+                ret.push(smaller);
+            }
+        }
+        for j in 0..maze[0].len() {
+            let column: String = maze.iter().map(|r| &r[j..=j]).collect();
+            if free(&column) {
+                ret.push(
+                    maze.iter()
+                        .map(|r| format!("{}{}", &r[..j], &r[j + 1..]))
+                        .collect(),
+                );
+            }
+        }
+        for (i, row) in maze.iter().enumerate() {
+            for (j, _) in row.match_indices('#') {
+                let mut smaller = maze.to_vec();
+                smaller[i].replace_range(j..=j, ".");
+                ret.push(smaller);
+            }
+        }
+        ret
+    }
+
+    /// Up to 7 nodes, with edges between a quarter of the pairs.
+    fn random_graph(rng: &mut Rng) -> (u8, Vec<(u8, u8)>) {
+        let n = rng.between(1, 7) as u8;
+        let edges = (0..n)
+            .flat_map(|a| (0..n).map(move |b| (a, b)))
+            .filter(|_| rng.one_in(4))
+            .collect();
+        (n, edges)
+    }
+
+    /// The graph without an edge, or without its last node.
+    fn shrink_graph((n, edges): &(u8, Vec<(u8, u8)>)) -> Vec<(u8, Vec<(u8, u8)>)> {
+        let mut ret: Vec<(u8, Vec<(u8, u8)>)> = shrink_vec(edges, |_| vec![])
+            .into_iter()
+            .map(|edges| (*n, edges))
+            .collect();
+        if *n > 1 {
+            let last = n - 1;
+            let edges = edges
+                .iter()
+                .filter(|&&(a, b)| a != last && b != last)
+                .copied()
+                .collect();
+            ret.push((last, edges));
+        }
+        ret
+    }
 
     #[test]
     fn test_ij() {
         let ij = IJ { i: 2, j: 3 };
+        let other = IJ::from((-1, 5));
+        assert_eq!(ij + other, IJ { i: 1, j: 8 });
+        assert_eq!(ij - other, IJ { i: 3, j: -2 });
+        assert_eq!(-ij, IJ { i: -2, j: -3 });
+        assert_eq!(2 * other - ij, IJ { i: -4, j: 7 });
+        assert_eq!(ij * 3, 3 * ij);
+        assert_eq!(<(isize, isize)>::from(ij), (2, 3));
+        assert_eq!(ij.distance(&other), 5);
+        assert_eq!(ij.chebyshev(&other), 3);
+        assert_eq!(
+            IJ { i: -1, j: 12 }.rem_euclid(IJ { i: 7, j: 5 }),
+            IJ { i: 6, j: 2 }
+        );
+        assert_eq!(
+            Direction::North.offset().rotate_right(),
+            Direction::East.offset()
+        );
+        assert_eq!(
+            Direction::North.offset().rotate_left(),
+            Direction::West.offset()
+        );
+        assert_eq!(ij.rotate_right().rotate_left(), ij);
+        assert_eq!(ij.rotate_right().rotate_right(), -ij);
         assert_eq!(ij.closer_than(1).collect::<Vec<IJ>>().len(), 5);
         assert_eq!(ij.closer_than(2).collect::<Vec<IJ>>().len(), 13);
         assert_eq!(ij.closer_than(3).collect::<Vec<IJ>>().len(), 25);
@@ -388,11 +1118,112 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_point() {
+        let p = Point([1, -2, 3]);
+        let q = Point([0, 0, 5]);
+        assert_eq!(p + q, Point([1, -2, 8]));
+        assert_eq!(p - q, Point([1, -2, -2]));
+        assert_eq!(-p * 2, Point([-2, 4, -6]));
+        assert_eq!(p.distance(&q), 5);
+        assert_eq!(p.chebyshev(&q), 2);
+        assert_eq!(p.rem_euclid(Point([3, 3, 3])), Point([1, 1, 0]));
+        assert_eq!(p.to_string(), "(1, -2, 3)");
+        assert_eq!(p.neighbors().count(), 6);
+        assert!(p.neighbors().all(|n| n.distance(&p) == 1));
+        assert_eq!(p.neighbors_diagonal().count(), 26);
+        assert_eq!(Point([0; 4]).neighbors().count(), 8);
+        assert_eq!(Point([0; 4]).neighbors_diagonal().count(), 80);
+        // octahedral numbers
+        assert_eq!(p.closer_than(1).count(), 7);
+        assert_eq!(p.closer_than(2).count(), 25);
+        assert_eq!(p.closer_than(-1).count(), 0);
+        let bounded: Vec<Point<3>> = Point::ORIGIN
+            .closer_than_and_bounded(1, Point::ORIGIN, Point([2, 2, 2]))
+            .collect();
+        assert_eq!(
+            bounded,
+            vec![
+                Point([0, 0, 0]),
+                Point([0, 0, 1]),
+                Point([0, 1, 0]),
+                Point([1, 0, 0])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_point_as_ij() {
+        let ij = IJ { i: 2, j: 3 };
+        let p = Point::from(ij);
+        assert_eq!(p, Point([2, 3]));
+        assert_eq!(IJ::from(p), ij);
+        assert_eq!(IJ::from(p + Point::from(ij)), ij * 2);
+        let (min, max) = (IJ { i: 0, j: 0 }, IJ { i: 4, j: 5 });
+        for d in 0..4 {
+            let mut from_ij: Vec<IJ> = ij.closer_than_and_bounded(d, min, max).collect();
+            let mut from_point: Vec<IJ> = p
+                .closer_than_and_bounded(d, min.into(), max.into())
+                .map(IJ::from)
+                .collect();
+            from_ij.sort();
+            from_point.sort();
+            assert_eq!(from_ij, from_point);
+        }
+        let mut neighbors: Vec<IJ> = p.neighbors().map(IJ::from).collect();
+        let mut expected = ij.neighbors().to_vec();
+        neighbors.sort();
+        expected.sort();
+        assert_eq!(neighbors, expected);
+    }
+
+    #[test]
+    fn test_direction() {
+        use Direction::*;
+        assert_eq!(North.turn_right(), East);
+        assert_eq!(North.turn_left(), West);
+        assert_eq!(East.turn_around(), West);
+        assert_eq!(West.turn_right().turn_right(), East);
+        let ij = IJ { i: 2, j: 3 };
+        assert_eq!(ij + North, IJ { i: 1, j: 3 });
+        assert_eq!(ij + East, IJ { i: 2, j: 4 });
+        assert_eq!(South.offset(), IJ { i: 1, j: 0 });
+        assert_eq!(
+            "^>v<"
+                .chars()
+                .filter_map(Direction::from_char)
+                .collect::<Vec<_>>(),
+            Direction::ALL
+        );
+        assert_eq!("W".parse(), Ok(West));
+        assert_eq!("↓".parse(), Ok(South));
+        assert!("x".parse::<Direction>().is_err());
+        assert!("NE".parse::<Direction>().is_err());
+        assert_eq!(East.to_string(), ">");
+
+        assert_eq!(Direction8::North.turn_right(), Direction8::NorthEast);
+        assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+        assert_eq!(Direction8::SouthEast.turn_around(), Direction8::NorthWest);
+        assert_eq!(ij + Direction8::SouthWest, IJ { i: 3, j: 2 });
+        assert_eq!(Direction8::from(West), Direction8::West);
+        assert_eq!(Direction8::West.straight(), Some(West));
+        assert_eq!(Direction8::NorthWest.straight(), None);
+        assert_eq!("NE".parse(), Ok(Direction8::NorthEast));
+        assert_eq!("↘".parse(), Ok(Direction8::SouthEast));
+        assert_eq!("<".parse(), Ok(Direction8::West));
+        assert!("NNE".parse::<Direction8>().is_err());
+        assert_eq!(Direction8::SouthWest.to_string(), "SW");
+        let total = Direction8::ALL
+            .iter()
+            .fold(IJ { i: 0, j: 0 }, |ij, &d| ij + d);
+        assert_eq!(total, IJ { i: 0, j: 0 });
+    }
+
     #[test]
     fn test_graph() {
         let g: DiGraph<u8> = DiGraph::from([1, 2, 3], [(1, 2), (2, 3)]);
         assert_eq!(g.len(), 3);
-        assert_eq!(g.edges.len(), 2);
+        assert_eq!(g.edge_count(), 2);
         assert_eq!(g.distance(1, 1), Some(0));
         assert_eq!(g.distance(1, 2), Some(1));
         assert_eq!(g.distance(1, 3), Some(2));
@@ -401,6 +1232,32 @@ pub mod tests {
 
         let g: DiGraph<u8> = DiGraph::from([1, 2, 3], [(1, 2), (2, 3), (1, 3)]);
         assert_eq!(g.distance(1, 3), Some(1));
+        assert_eq!(g.distances_from(2), HashMap::from([(2, 0), (3, 1)]));
+    }
+
+    #[test]
+    fn test_graph_index() {
+        let mut g: DiGraph<char> = DiGraph::new();
+        assert!(g.is_empty());
+        assert_eq!(g.add_node('a'), 0);
+        assert_eq!(g.add_node('b'), 1);
+        assert_eq!(g.add_node('a'), 0);
+        assert!(g.add_edge('a', 'b'));
+        assert!(!g.add_edge('a', 'b'));
+        assert!(g.add_edge('a', 'c'));
+        assert!(g.add_edge('c', 'b'));
+        assert_eq!(g.nodes(), &['a', 'b', 'c']);
+        assert_eq!(g.edge_count(), 3);
+        assert_eq!(
+            g.edges().collect::<Vec<_>>(),
+            vec![(&'a', &'b'), (&'a', &'c'), (&'c', &'b')]
+        );
+        assert_eq!(g.children(&'a').collect::<Vec<_>>(), vec![&'b', &'c']);
+        assert_eq!(g.parents(&'b').collect::<Vec<_>>(), vec![&'a', &'c']);
+        assert_eq!(g.children(&'z').count(), 0);
+        assert_eq!((g.degree(&'a'), g.degree(&'b')), (2, 0));
+        assert!(g.contains(&'c') && !g.contains(&'z'));
+        assert!(g.contains_edge(&'c', &'b') && !g.contains_edge(&'b', &'c'));
     }
 
     #[test]
@@ -411,6 +1268,9 @@ pub mod tests {
         assert_eq!(ds.get(&1, &2), Some(1));
         assert_eq!(ds.get(&1, &3), Some(2));
         assert_eq!(ds.get(&3, &1), None);
+
+        let g: DiGraph<u8> = DiGraph::from([1], [(1, 1)]);
+        assert_eq!(g.all_distances().get(&1, &1), Some(0));
     }
 
     #[test]
@@ -468,4 +1328,72 @@ pub mod tests {
         let m = Maze::from(&mz);
         assert_eq!(m.distance(), Some(4));
     }
+
+    #[test]
+    fn test_grid() {
+        let digit = |c: char| c.to_digit(10).map(|d| d as u8);
+        let mut g = Grid::parse(&lines("123\n456\n"), "a digit", digit).unwrap();
+        assert_eq!((g.height(), g.width()), (2, 3));
+        assert_eq!(g.get(IJ { i: 1, j: 0 }), Some(&4));
+        assert_eq!(g.get(IJ { i: 2, j: 0 }), None);
+        assert_eq!(g.get(IJ { i: 0, j: -1 }), None);
+        *g.get_mut(IJ { i: 0, j: 2 }).unwrap() = 9;
+        assert_eq!(g.rows().collect::<Vec<_>>(), vec![[1, 2, 9], [4, 5, 6]]);
+        assert_eq!(g.column(1).collect::<Vec<_>>(), vec![&2, &5]);
+        assert_eq!(g.column(3).count(), 0);
+        assert_eq!(g.cells().nth(4), Some((IJ { i: 1, j: 1 }, &5)));
+        assert_eq!(g.find(&5), Some(IJ { i: 1, j: 1 }));
+        assert_eq!(g.find(&7), None);
+        assert_eq!(g.to_string(), "129\n456\n");
+
+        let corner = IJ { i: 0, j: 0 };
+        assert_eq!(g.neighbors4(corner).count(), 2);
+        assert_eq!(g.neighbors8(corner).count(), 3);
+        assert_eq!(g.neighbors8(IJ { i: 1, j: 1 }).count(), 5);
+
+        let e = Grid::parse(&lines("12\n3x\n"), "a digit", digit).unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 2, "x"));
+        let e = Grid::parse(&lines("12\n345\n"), "a digit", digit).unwrap_err();
+        assert_eq!(e.line, 2);
+        assert!(Grid::parse(&lines(""), "a digit", digit).is_err());
+
+        let mut g = Grid::new(2, 2, '.');
+        *g.get_mut(IJ { i: 1, j: 0 }).unwrap() = '#';
+        assert_eq!(g.positions(&'.').count(), 3);
+        assert_eq!(g.to_string(), "..\n#.\n");
+    }
+
+    #[test]
+    fn test_maze_distance_as_digraph() {
+        assert_agree(
+            "Maze::distance and DiGraph::distance",
+            300,
+            random_maze,
+            |maze| shrink_maze(maze),
+            |maze| Maze::from(maze).distance(),
+            |maze| {
+                let m = Maze::from(maze);
+                m.as_digraph().distance(m.start, m.end)
+            },
+        );
+    }
+
+    #[test]
+    fn test_all_distances_as_distance() {
+        let pairs = |n: u8| (0..n).flat_map(move |a| (0..n).map(move |b| (a, b)));
+        assert_agree(
+            "DiGraph::distance and all_distances",
+            300,
+            random_graph,
+            shrink_graph,
+            |(n, edges)| {
+                let g = DiGraph::from(0..*n, edges.clone());
+                pairs(*n).map(|(a, b)| g.distance(a, b)).collect::<Vec<_>>()
+            },
+            |(n, edges)| {
+                let ds = DiGraph::from(0..*n, edges.clone()).all_distances();
+                pairs(*n).map(|(a, b)| ds.get(&a, &b)).collect::<Vec<_>>()
+            },
+        );
+    }
 }
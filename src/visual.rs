@@ -0,0 +1,230 @@
+use std::{
+    cell::RefCell,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+    thread,
+    time::Duration,
+};
+
+use crate::{input::day_path, part::Part, solution::day_label};
+
+/// Where the frames of a visualized run go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    /// redrawn in the terminal, on stderr
+    Ansi,
+    /// a file per frame, in `DIR/day_N/part_P/00001.txt` and so on
+    Dir(PathBuf),
+}
+
+impl Target {
+    /// `ansi` means the terminal, anything else is a directory.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "ansi" => Self::Ansi,
+            dir => Self::Dir(PathBuf::from(dir)),
+        }
+    }
+}
+
+/// How long each frame stays in the terminal.
+pub const FRAME_DELAY: Duration = Duration::from_millis(50);
+
+static TARGET: OnceLock<Target> = OnceLock::new();
+
+/// The part being solved in this thread, and how many frames it drew.
+struct Scope {
+    label: String,
+    part: Part,
+    dir: Option<PathBuf>,
+    frames: usize,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Scope>> = const { RefCell::new(None) };
+}
+
+/// Makes the days draw frames to `target` from now on. Only the first call
+/// does something.
+pub fn enable(target: Target) {
+    let _ = TARGET.set(target);
+}
+
+pub fn enabled() -> bool {
+    TARGET.get().is_some()
+}
+
+/// Frames of a previous run, which would get mixed with the new ones.
+fn remove_frames(dir: &Path) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_frame = path.extension().is_some_and(|e| e == "txt")
+            && path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .is_some_and(|s| s.chars().all(|c| c.is_ascii_digit()));
+        if is_frame {
+            fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Calls `f`, which solves `part` of `day`, so that the frames it draws are
+/// of that part, if visualizing.
+pub fn scope<T>(year: usize, day: usize, part: Part, f: impl FnOnce() -> T) -> T {
+    match TARGET.get() {
+        Some(target) => scope_to(target, year, day, part, f),
+        None => f(),
+    }
+}
+
+fn scope_to<T>(target: &Target, year: usize, day: usize, part: Part, f: impl FnOnce() -> T) -> T {
+    let dir = match target {
+        Target::Ansi => None,
+        Target::Dir(dir) => {
+            let dir = day_path(dir, year, day).join(format!("part_{part}"));
+            match fs::create_dir_all(&dir).and_then(|()| remove_frames(&dir)) {
+                Ok(()) => Some(dir),
+                Err(e) => {
+                    eprintln!("warning: not visualizing, can't use {}: {e}", dir.display());
+                    return f();
+                }
+            }
+        }
+    };
+    let scope = Scope {
+        label: day_label(year, day),
+        part,
+        dir,
+        frames: 0,
+    };
+    let _outer = Restore(CURRENT.replace(Some(scope)));
+    f()
+}
+
+/// Puts back the scope of an outer call when dropped, even if the solver
+/// panicked, so that the thread doesn't keep drawing for it.
+struct Restore(Option<Scope>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT.set(self.0.take());
+    }
+}
+
+/// Whether frames drawn now go anywhere.
+pub fn active() -> bool {
+    CURRENT.with_borrow(Option::is_some)
+}
+
+/// Draws the frame `render` gives, if visualizing. Only then is it called.
+pub fn frame(render: impl FnOnce() -> String) {
+    CURRENT.with_borrow_mut(|current| {
+        let Some(scope) = current else {
+            return;
+        };
+        scope.frames += 1;
+        let text = render();
+        match &scope.dir {
+            None => {
+                let mut err = io::stderr().lock();
+                let _ = write!(
+                    err,
+                    "\x1b[2J\x1b[H{} part {}, frame {}\n{text}",
+                    scope.label, scope.part, scope.frames
+                );
+                let _ = err.flush();
+                thread::sleep(FRAME_DELAY);
+            }
+            Some(dir) => {
+                let path = dir.join(format!("{:05}.txt", scope.frames));
+                if let Err(e) = fs::write(&path, text) {
+                    eprintln!(
+                        "warning: stopped visualizing, can't write {}: {e}",
+                        path.display()
+                    );
+                    *current = None;
+                }
+            }
+        }
+    });
+}
+
+/// `rows` by `cols` of `empty`, with `marks` on them where their cells say.
+/// Rows and columns outside are ignored.
+pub fn draw(rows: usize, cols: usize, empty: char, marks: &[(char, &[(usize, usize)])]) -> String {
+    let mut grid = vec![vec![empty; cols]; rows];
+    for (c, cells) in marks {
+        for &(i, j) in cells.iter() {
+            if let Some(cell) = grid.get_mut(i).and_then(|row| row.get_mut(j)) {
+                *cell = *c;
+            }
+        }
+    }
+    let mut ret = String::new();
+    for row in grid {
+        ret.extend(row);
+        ret.push('\n');
+    }
+    ret
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::{fs, panic};
+
+    use super::{active, draw, frame, scope_to, Target};
+    use crate::part::Part;
+
+    #[test]
+    fn test_draw() {
+        assert_eq!(
+            draw(
+                2,
+                3,
+                '.',
+                &[('#', &[(0, 0), (1, 2), (5, 5)]), ('@', &[(0, 0)])]
+            ),
+            "@..\n..#\n"
+        );
+    }
+
+    #[test]
+    fn test_frames() {
+        assert_eq!(Target::from_arg("ansi"), Target::Ansi);
+        let dir = std::env::temp_dir().join(format!("aoc_2024_visual_test_{}", std::process::id()));
+        let frames = dir.join("2023").join("day_5").join("part_2");
+        fs::create_dir_all(&frames).unwrap();
+        fs::write(frames.join("00007.txt"), "old").unwrap();
+        fs::write(frames.join("notes.txt"), "mine").unwrap();
+
+        // nothing is drawn outside a scope
+        frame(|| panic!("not visualizing"));
+        assert!(!active());
+        let target = Target::from_arg(dir.to_str().unwrap());
+        let was_active = scope_to(&target, 2023, 5, Part::Two, || {
+            frame(|| "1\n".to_string());
+            frame(|| "2\n".to_string());
+            active()
+        });
+        assert!(!active());
+        let mut names: Vec<String> = fs::read_dir(&frames)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        let second = fs::read_to_string(frames.join("00002.txt"));
+        let panicked =
+            panic::catch_unwind(|| scope_to(&target, 2023, 5, Part::Two, || panic!("solver bug")));
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(panicked.is_err());
+        assert!(!active());
+
+        assert!(was_active);
+        assert_eq!(names, vec!["00001.txt", "00002.txt", "notes.txt"]);
+        assert_eq!(second.unwrap(), "2\n");
+    }
+}
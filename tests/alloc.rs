@@ -0,0 +1,44 @@
+//! On its own, so that counting allocations doesn't change other tests.
+
+use aoc_2024::alloc::{enable, measure, AllocStats, Counting};
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+#[test]
+fn test_measure() {
+    enable();
+    let (v, stats) = measure(|| {
+        let mut v: Vec<u64> = Vec::with_capacity(100);
+        v.push(1);
+        let w = vec![0u8; 1000];
+        drop(w);
+        v
+    });
+    assert_eq!(
+        stats,
+        Some(AllocStats {
+            allocations: 2,
+            bytes: 1800,
+            peak: 1800
+        })
+    );
+    let (_, stats) = measure(|| drop(v));
+    assert_eq!(
+        stats,
+        Some(AllocStats {
+            allocations: 0,
+            bytes: 0,
+            peak: 0
+        })
+    );
+    // growing reallocates
+    let (v, stats) = measure(|| {
+        let mut v: Vec<u64> = Vec::with_capacity(1);
+        v.extend(0..2);
+        v
+    });
+    let stats = stats.unwrap();
+    assert_eq!(stats.allocations, 2);
+    assert_eq!(stats.peak, 8 * v.capacity());
+}
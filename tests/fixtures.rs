@@ -0,0 +1,23 @@
+use std::path::Path;
+
+use aoc_2024::{fixtures, registry, solution::day_label};
+
+#[test]
+fn test_fixtures() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(fixtures::DEFAULT_DIR);
+    let fixtures = fixtures::discover(&dir).unwrap_or_else(|e| panic!("{e}"));
+    let registry = registry::all();
+    for solver in registry.iter() {
+        let (year, day) = (solver.year(), solver.day());
+        assert!(
+            fixtures.iter().any(|f| (f.year, f.day) == (year, day)),
+            "no fixture for {}",
+            day_label(year, day)
+        );
+    }
+    let problems: Vec<String> = fixtures
+        .iter()
+        .flat_map(|f| fixtures::check(&registry, f))
+        .collect();
+    assert!(problems.is_empty(), "\n{}", problems.join("\n"));
+}
@@ -0,0 +1,28 @@
+use aoc_2024::{
+    answer::Answer,
+    day_11::Day11,
+    part::Part,
+    registry,
+    solution::{Params, Solution},
+    utils::DiGraph,
+};
+
+#[test]
+fn test_solvers() {
+    let registry = registry::all();
+    let day_9 = registry.get(2024, 9).unwrap();
+    let parsed = day_9.parse("2333133121414131402\n").unwrap();
+    let params = Params::defaults(day_9.params());
+    assert_eq!(day_9.solve(&parsed, Part::One, &params), Answer::Int(1928));
+
+    let input = Day11::parse("125 17").unwrap();
+    let params = Params::new(Day11::PARAMS, &[("blinks1".to_string(), 6)]).unwrap();
+    assert_eq!(Day11::part1(&input, &params), Answer::Int(22));
+}
+
+#[test]
+fn test_utils() {
+    let graph = DiGraph::from([1, 2, 3], [(1, 2), (2, 3)]);
+    assert_eq!(graph.distance(1, 3), Some(2));
+    assert_eq!(graph.distance(3, 1), None);
+}